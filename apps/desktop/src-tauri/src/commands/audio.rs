@@ -1,12 +1,52 @@
 // Audio Commands - Tauri interface for audio operations
-// Exposes audio device management to the frontend
+// Exposes device enumeration, live stream control, aggregate-device
+// management, and recording to the frontend, all backed by one
+// process-lifetime `AudioGraph`/`StreamManager`/`Recorder` so state (which
+// aggregate devices exist, whether a stream is running) survives across
+// invocations instead of resetting on every command.
 
-use crate::services::{AudioDevice, DeviceManager};
+use std::sync::{Arc, Mutex};
 
-/// Get all available audio devices (inputs and outputs)
+use espectro::AudioGraph;
+use tauri::State;
+
+use crate::services::{
+    AudioDevice, DeviceManager, ParameterSnapshot, Recorder, RecordingStatus, StreamManager,
+    StreamStatus,
+};
+
+/// Tauri-managed state holding the live audio engine: the processing graph,
+/// device enumeration/aggregation, stream lifecycle, and the recorder tapped
+/// into every running stream. Kept together (rather than four separate
+/// `manage` calls) because `StreamManager` is built around shared handles to
+/// the graph and recorder.
+pub struct AudioEngineState {
+    graph: Arc<Mutex<AudioGraph>>,
+    devices: Mutex<DeviceManager>,
+    streams: Mutex<StreamManager>,
+    recorder: Arc<Mutex<Recorder>>,
+}
+
+impl Default for AudioEngineState {
+    fn default() -> Self {
+        let graph = Arc::new(Mutex::new(AudioGraph::new()));
+        let recorder = Arc::new(Mutex::new(Recorder::new()));
+        let streams = Mutex::new(StreamManager::new(Arc::clone(&graph), Arc::clone(&recorder)));
+
+        Self {
+            graph,
+            devices: Mutex::new(DeviceManager::new()),
+            streams,
+            recorder,
+        }
+    }
+}
+
+/// Get all available audio devices (inputs, outputs, and any aggregate
+/// devices created this session)
 #[tauri::command]
-pub fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
-    let manager = DeviceManager::new();
+pub fn get_audio_devices(state: State<'_, AudioEngineState>) -> Result<Vec<AudioDevice>, String> {
+    let manager = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
     manager
         .list_devices()
         .map_err(|e| format!("Failed to enumerate audio devices: {}", e))
@@ -14,8 +54,10 @@ pub fn get_audio_devices() -> Result<Vec<AudioDevice>, String> {
 
 /// Get the default input device
 #[tauri::command]
-pub fn get_default_input_device() -> Result<Option<AudioDevice>, String> {
-    let manager = DeviceManager::new();
+pub fn get_default_input_device(
+    state: State<'_, AudioEngineState>,
+) -> Result<Option<AudioDevice>, String> {
+    let manager = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
     manager
         .default_input_device()
         .map_err(|e| format!("Failed to get default input device: {}", e))
@@ -23,9 +65,202 @@ pub fn get_default_input_device() -> Result<Option<AudioDevice>, String> {
 
 /// Get the default output device
 #[tauri::command]
-pub fn get_default_output_device() -> Result<Option<AudioDevice>, String> {
-    let manager = DeviceManager::new();
+pub fn get_default_output_device(
+    state: State<'_, AudioEngineState>,
+) -> Result<Option<AudioDevice>, String> {
+    let manager = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
     manager
         .default_output_device()
         .map_err(|e| format!("Failed to get default output device: {}", e))
 }
+
+/// Bundle devices into a clock-synchronized aggregate device; see
+/// `DeviceManager::create_aggregate`
+#[tauri::command]
+pub fn create_aggregate_device(
+    state: State<'_, AudioEngineState>,
+    master_id: String,
+    member_ids: Vec<String>,
+) -> Result<AudioDevice, String> {
+    let mut manager = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    manager
+        .create_aggregate(&master_id, &member_ids)
+        .map_err(|e| format!("Failed to create aggregate device: {}", e))
+}
+
+/// Tear down a previously created aggregate device
+#[tauri::command]
+pub fn destroy_aggregate_device(
+    state: State<'_, AudioEngineState>,
+    id: String,
+) -> Result<(), String> {
+    let mut manager = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    manager
+        .destroy_aggregate(&id)
+        .map_err(|e| format!("Failed to destroy aggregate device: {}", e))
+}
+
+/// Start capturing `device` and running it through the graph (see
+/// `StreamManager::start_input`)
+#[tauri::command]
+pub fn start_input_stream(
+    state: State<'_, AudioEngineState>,
+    device: AudioDevice,
+) -> Result<StreamStatus, String> {
+    let devices = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    let mut streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    streams
+        .start_input(&devices, &device)
+        .map_err(|e| format!("Failed to start input stream: {}", e))
+}
+
+/// Start playing the graph's output (fed with silence) to `device`; see
+/// `StreamManager::start_output`
+#[tauri::command]
+pub fn start_output_stream(
+    state: State<'_, AudioEngineState>,
+    device: AudioDevice,
+) -> Result<StreamStatus, String> {
+    let devices = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    let mut streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    streams
+        .start_output(&devices, &device)
+        .map_err(|e| format!("Failed to start output stream: {}", e))
+}
+
+/// Start a combined input+output stream routed through the graph; see
+/// `StreamManager::start_duplex`
+#[tauri::command]
+pub fn start_duplex_stream(
+    state: State<'_, AudioEngineState>,
+    input: AudioDevice,
+    output: AudioDevice,
+) -> Result<StreamStatus, String> {
+    let devices = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    let mut streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    streams
+        .start_duplex(&devices, &input, &output)
+        .map_err(|e| format!("Failed to start duplex stream: {}", e))
+}
+
+/// Start streaming the graph's output across a previously created aggregate
+/// device; see `StreamManager::start_aggregate_output`
+#[tauri::command]
+pub fn start_aggregate_output_stream(
+    state: State<'_, AudioEngineState>,
+    aggregate_id: String,
+) -> Result<StreamStatus, String> {
+    let devices = state.devices.lock().map_err(|_| "Device manager lock poisoned".to_string())?;
+    let mut streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    streams
+        .start_aggregate_output(&devices, &aggregate_id)
+        .map_err(|e| format!("Failed to start aggregate output stream: {}", e))
+}
+
+/// Stop and drop whatever stream is currently running
+#[tauri::command]
+pub fn stop_audio_stream(state: State<'_, AudioEngineState>) -> Result<StreamStatus, String> {
+    let mut streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    Ok(streams.stop())
+}
+
+/// Current stream health snapshot, for a UI to poll
+#[tauri::command]
+pub fn audio_stream_status(state: State<'_, AudioEngineState>) -> Result<StreamStatus, String> {
+    let streams = state.streams.lock().map_err(|_| "Stream manager lock poisoned".to_string())?;
+    Ok(streams.status())
+}
+
+/// Ramp a node's parameter to `value` over `ramp_ms` milliseconds instead of
+/// snapping it instantly, so a UI control being dragged doesn't produce
+/// zipper noise on the live graph; see `AudioGraph::set_parameter_smoothed`.
+#[tauri::command]
+pub fn set_node_parameter_smoothed(
+    state: State<'_, AudioEngineState>,
+    node_id: String,
+    parameter: String,
+    value: f32,
+    ramp_ms: f32,
+) -> Result<(), String> {
+    let mut graph = state.graph.lock().map_err(|_| "Audio graph lock poisoned".to_string())?;
+    graph
+        .set_parameter_smoothed(&node_id, &parameter, value, ramp_ms)
+        .map_err(|e| format!("Failed to set parameter: {}", e))
+}
+
+/// Start recording whatever is live on the current stream to `path`; the
+/// stream taps every processed block to the same recorder (see
+/// `StreamManager`'s `recorder` field), so this just points it at a file.
+#[tauri::command]
+pub fn start_recording(
+    state: State<'_, AudioEngineState>,
+    path: String,
+    sample_rate: u32,
+    channels: usize,
+) -> Result<(), String> {
+    let parameters = {
+        let graph = state.graph.lock().map_err(|_| "Audio graph lock poisoned".to_string())?;
+        snapshot_parameters(&graph)
+    };
+
+    let mut recorder = state.recorder.lock().map_err(|_| "Recorder lock poisoned".to_string())?;
+    recorder
+        .start(path, sample_rate, channels, parameters)
+        .map_err(|e| format!("Failed to start recording: {}", e))
+}
+
+/// Pause the current recording without stopping the writer thread
+#[tauri::command]
+pub fn pause_recording(state: State<'_, AudioEngineState>) -> Result<(), String> {
+    let mut recorder = state.recorder.lock().map_err(|_| "Recorder lock poisoned".to_string())?;
+    recorder.pause();
+    Ok(())
+}
+
+/// Resume a paused recording
+#[tauri::command]
+pub fn resume_recording(state: State<'_, AudioEngineState>) -> Result<(), String> {
+    let mut recorder = state.recorder.lock().map_err(|_| "Recorder lock poisoned".to_string())?;
+    recorder.resume();
+    Ok(())
+}
+
+/// Stop recording, flush the writer thread, and finalize the file
+#[tauri::command]
+pub fn finalize_recording(state: State<'_, AudioEngineState>) -> Result<RecordingStatus, String> {
+    let mut recorder = state.recorder.lock().map_err(|_| "Recorder lock poisoned".to_string())?;
+    recorder
+        .finalize()
+        .map_err(|e| format!("Failed to finalize recording: {}", e))
+}
+
+/// Current recording health/progress snapshot, for a UI to show a timer
+#[tauri::command]
+pub fn recording_status(state: State<'_, AudioEngineState>) -> Result<RecordingStatus, String> {
+    let recorder = state.recorder.lock().map_err(|_| "Recorder lock poisoned".to_string())?;
+    Ok(recorder.status())
+}
+
+/// Snapshot every node's current parameter values, so a recording's sidecar
+/// metadata can always be traced back to the settings that produced it
+fn snapshot_parameters(graph: &AudioGraph) -> Vec<ParameterSnapshot> {
+    graph
+        .node_ids()
+        .into_iter()
+        .filter_map(|node_id| {
+            let metadata = graph.node_metadata(&node_id).ok()?;
+            let parameters = metadata
+                .parameters
+                .iter()
+                .filter_map(|descriptor| {
+                    graph
+                        .get_parameter(&node_id, &descriptor.name)
+                        .ok()
+                        .flatten()
+                        .map(|value| (descriptor.name.clone(), value))
+                })
+                .collect();
+            Some(ParameterSnapshot { node_id, parameters })
+        })
+        .collect()
+}
@@ -0,0 +1,35 @@
+/// Save/load commands for a serialized audio-processing graph
+use std::fs;
+
+use espectro::{AudioGraph, GraphDocument};
+
+use crate::services::GraphFactory;
+
+/// Write a `GraphDocument` (nodes, parameters, connections) to `path` as JSON
+#[tauri::command]
+pub async fn save_graph(path: String, document: GraphDocument) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(&document)
+        .map_err(|e| format!("Failed to serialize graph: {}", e))?;
+
+    fs::write(&path, json).map_err(|e| format!("Failed to write graph file: {}", e))
+}
+
+/// Load a `GraphDocument` from `path`, validating that every node's type tag
+/// is one this build of the app actually knows how to construct
+#[tauri::command]
+pub async fn load_graph(path: String) -> Result<GraphDocument, String> {
+    let contents = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read graph file: {}", e))?;
+
+    let document: GraphDocument = serde_json::from_str(&contents)
+        .map_err(|e| format!("Failed to parse graph file: {}", e))?;
+
+    // Validate by reconstructing into a scratch graph, which is then dropped;
+    // loading a document does not touch the engine's live AudioGraph.
+    let mut scratch = AudioGraph::new();
+    document
+        .apply_to_graph(&mut scratch, &GraphFactory)
+        .map_err(|e| format!("Invalid graph document: {}", e))?;
+
+    Ok(document)
+}
@@ -0,0 +1,8 @@
+/// Runtime log-level control
+use tauri::State;
+use crate::logging::LogHandle;
+
+#[tauri::command]
+pub fn set_log_level(level: String, state: State<'_, LogHandle>) -> Result<(), String> {
+    state.set_level(&level)
+}
@@ -0,0 +1,64 @@
+/// ReplayGain / EBU R128 loudness analysis commands
+use tauri::{Emitter, WebviewWindow};
+
+use crate::models::{AlbumLoudness, LoudnessInfo, ScanProgress};
+use crate::services::LoudnessAnalyzer;
+
+/// Measure integrated loudness and suggested gain for a batch of scanned
+/// files, reporting progress the same way `scan_directory` does. Pass
+/// `write_tags` to also persist the result as ReplayGain tags on each file.
+#[tauri::command]
+pub async fn analyze_library_loudness(
+    window: WebviewWindow,
+    file_paths: Vec<String>,
+    write_tags: bool,
+) -> Result<Vec<LoudnessInfo>, String> {
+    let analyzer = LoudnessAnalyzer::new();
+    let total = file_paths.len();
+    let mut results = Vec::with_capacity(total);
+
+    for (index, path) in file_paths.into_iter().enumerate() {
+        let info = match analyzer.analyze(&path) {
+            Ok(info) => info,
+            Err(e) => {
+                eprintln!("Skipping loudness analysis for {}: {}", path, e);
+                continue;
+            }
+        };
+
+        if write_tags {
+            if let Err(e) = LoudnessAnalyzer::write_replaygain_tags(&path, &info) {
+                eprintln!("Failed to write ReplayGain tags for {}: {}", path, e);
+            }
+        }
+
+        let _ = window.emit("scan-progress", ScanProgress::new(index + 1, total, path.clone()));
+        results.push(info);
+    }
+
+    let _ = window.emit("scan-complete", ScanProgress::completed(results.len()));
+    Ok(results)
+}
+
+/// Measure a whole album in one gated pass so every track gets the same
+/// album gain instead of being normalized independently.
+#[tauri::command]
+pub async fn analyze_album_loudness(
+    window: WebviewWindow,
+    file_paths: Vec<String>,
+    write_tags: bool,
+) -> Result<AlbumLoudness, String> {
+    let analyzer = LoudnessAnalyzer::new();
+    let album = analyzer.analyze_album(&file_paths)?;
+
+    if write_tags {
+        for info in &album.tracks {
+            if let Err(e) = LoudnessAnalyzer::write_replaygain_tags(&info.file_path, info) {
+                eprintln!("Failed to write ReplayGain tags for {}: {}", info.file_path, e);
+            }
+        }
+    }
+
+    let _ = window.emit("scan-complete", ScanProgress::completed(album.tracks.len()));
+    Ok(album)
+}
@@ -1,9 +1,25 @@
-/// Metadata extraction command
+/// Metadata extraction/writing commands
+use std::path::Path;
+
 use crate::models::AudioMetadata;
-use crate::services::MetadataExtractor;
+use crate::services::{handler_for, MetadataExtractor, SecurityValidator};
 
 #[tauri::command]
 pub async fn extract_metadata(file_path: String) -> Result<AudioMetadata, String> {
     let extractor = MetadataExtractor::new();
     extractor.extract(&file_path)
 }
+
+/// Write edited metadata back to `file_path`, dispatching to the right
+/// `TagHandler` for its actual container format (sniffed, not guessed from
+/// the extension). Every string is re-sanitized and any cover art
+/// re-validated here too, so a write can't be used to smuggle in anything
+/// a read would have stripped.
+#[tauri::command]
+pub async fn write_metadata(file_path: String, metadata: AudioMetadata) -> Result<(), String> {
+    let path = Path::new(&file_path);
+    SecurityValidator::validate_audio_file(path)?;
+
+    let handler = handler_for(path)?;
+    handler.write(path, &metadata)
+}
@@ -3,9 +3,23 @@ pub mod audio;
 pub mod folder;
 pub mod scanner;
 pub mod metadata;
+pub mod playlist;
+pub mod graph;
+pub mod loudness;
+pub mod logging;
 
 pub use app::{exit_app, hide_window};
-pub use audio::{get_audio_devices, get_default_input_device, get_default_output_device};
+pub use audio::{
+    audio_stream_status, create_aggregate_device, destroy_aggregate_device, finalize_recording,
+    get_audio_devices, get_default_input_device, get_default_output_device, pause_recording,
+    recording_status, resume_recording, set_node_parameter_smoothed, start_aggregate_output_stream,
+    start_duplex_stream, start_input_stream, start_output_stream, start_recording,
+    stop_audio_stream, AudioEngineState,
+};
 pub use folder::select_folder;
-pub use scanner::scan_directory;
-pub use metadata::extract_metadata;
+pub use scanner::{cancel_scan, scan_directory, ScanState};
+pub use metadata::{extract_metadata, write_metadata};
+pub use playlist::{analyze_library_features, generate_playlist};
+pub use graph::{load_graph, save_graph};
+pub use loudness::{analyze_album_loudness, analyze_library_loudness};
+pub use logging::set_log_level;
@@ -0,0 +1,173 @@
+/// Similarity-based playlist generation
+use std::path::Path;
+
+use tauri::{AppHandle, Manager};
+
+use crate::models::AudioFeatures;
+use crate::services::{FeatureCache, FeatureExtractor};
+
+/// Analyze a batch of scanned files and cache their feature descriptors,
+/// skipping any file whose content hash is already cached. The hash is
+/// just a read of the raw bytes, so checking it against the cache *before*
+/// calling `extract` (which decodes and FFTs the whole file) is what makes
+/// a rescan of a mostly-unchanged library actually cheap.
+#[tauri::command]
+pub async fn analyze_library_features(
+    app_handle: AppHandle,
+    file_paths: Vec<String>,
+) -> Result<usize, String> {
+    let cache_path = FeatureCache::default_path(
+        &app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?,
+    );
+
+    let mut cache = FeatureCache::load(cache_path);
+    let extractor = FeatureExtractor::new();
+    let mut analyzed = 0;
+
+    for path in file_paths {
+        let content_hash = match FeatureExtractor::content_hash(Path::new(&path)) {
+            Ok(hash) => hash,
+            Err(e) => {
+                eprintln!("Skipping feature extraction for {}: {}", path, e);
+                continue;
+            }
+        };
+
+        if cache.get(&content_hash).is_some() {
+            continue;
+        }
+
+        let features = match extractor.extract(&path) {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("Skipping feature extraction for {}: {}", path, e);
+                continue;
+            }
+        };
+
+        cache.insert(features);
+        analyzed += 1;
+    }
+
+    cache.save()?;
+    Ok(analyzed)
+}
+
+/// Generate a "sounds-like" playlist of `count` tracks, ordered by increasing
+/// distance from `seed_path`, picking the nearest unused neighbor at each step.
+#[tauri::command]
+pub async fn generate_playlist(
+    app_handle: AppHandle,
+    seed_path: String,
+    count: usize,
+) -> Result<Vec<String>, String> {
+    let cache_path = FeatureCache::default_path(
+        &app_handle
+            .path()
+            .app_data_dir()
+            .map_err(|e| format!("Failed to resolve app data dir: {}", e))?,
+    );
+
+    let mut cache = FeatureCache::load(cache_path);
+    let extractor = FeatureExtractor::new();
+
+    let seed_content_hash = FeatureExtractor::content_hash(Path::new(&seed_path))?;
+    let seed_features = match cache.get(&seed_content_hash) {
+        Some(cached) => AudioFeatures {
+            source_path: seed_path.clone(),
+            ..cached.clone()
+        },
+        None => {
+            let features = extractor.extract(&seed_path)?;
+            cache.insert(features.clone());
+            cache.save()?;
+            features
+        }
+    };
+
+    // The candidate pool is every other track already in the cache (keyed by
+    // content hash, each entry carrying the `source_path` it was extracted
+    // from); the seed itself is excluded by its content hash.
+    let normalized = normalize(&seed_features, cache.values());
+
+    let mut remaining: Vec<&AudioFeatures> = cache.values()
+        .filter(|f| f.content_hash != seed_features.content_hash)
+        .collect();
+
+    let mut ordered = Vec::new();
+    let mut current = normalized.get(&seed_features.content_hash)
+        .cloned()
+        .unwrap_or_else(|| seed_features.as_vector());
+
+    while ordered.len() < count && !remaining.is_empty() {
+        let (idx, _) = remaining
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let v = normalized.get(&f.content_hash).cloned().unwrap_or_else(|| f.as_vector());
+                (i, euclidean(&current, &v))
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+            .ok_or("No candidates remaining")?;
+
+        let chosen = remaining.remove(idx);
+        current = normalized.get(&chosen.content_hash).cloned().unwrap_or_else(|| chosen.as_vector());
+        ordered.push(chosen.source_path.clone());
+    }
+
+    Ok(ordered)
+}
+
+/// Z-score each dimension across the seed + library so no single feature
+/// (e.g. tempo, which spans a much larger numeric range than ZCR) dominates
+/// the distance calculation.
+fn normalize<'a>(
+    seed: &AudioFeatures,
+    library: impl Iterator<Item = &'a AudioFeatures>,
+) -> std::collections::HashMap<String, Vec<f32>> {
+    let mut all: Vec<&AudioFeatures> = library.collect();
+    if !all.iter().any(|f| f.content_hash == seed.content_hash) {
+        all.push(seed);
+    }
+
+    let dims = seed.as_vector().len();
+    let mut means = vec![0.0f32; dims];
+    let mut stddevs = vec![0.0f32; dims];
+
+    for f in &all {
+        let v = f.as_vector();
+        for d in 0..dims {
+            means[d] += v[d];
+        }
+    }
+    for m in &mut means {
+        *m /= all.len().max(1) as f32;
+    }
+
+    for f in &all {
+        let v = f.as_vector();
+        for d in 0..dims {
+            stddevs[d] += (v[d] - means[d]).powi(2);
+        }
+    }
+    for s in &mut stddevs {
+        *s = (*s / all.len().max(1) as f32).sqrt().max(1e-6);
+    }
+
+    all.iter()
+        .map(|f| {
+            let v = f.as_vector();
+            let normalized: Vec<f32> = v.iter().enumerate()
+                .map(|(d, &x)| (x - means[d]) / stddevs[d])
+                .collect();
+            (f.content_hash.clone(), normalized)
+        })
+        .collect()
+}
+
+fn euclidean(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y).powi(2)).sum::<f32>().sqrt()
+}
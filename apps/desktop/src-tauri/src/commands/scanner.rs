@@ -1,14 +1,28 @@
 /// Directory scanning command
 use std::path::PathBuf;
-use tauri::WebviewWindow;
-use crate::services::FileScanner;
+use std::sync::Arc;
+use tauri::{State, WebviewWindow};
+use crate::services::{FileScanner, ScanCancellation};
+
+/// Tauri-managed state holding the cancellation flag for whatever scan is
+/// currently in flight, so a separate `cancel_scan` invocation can reach it.
+#[derive(Default)]
+pub struct ScanState(pub Arc<ScanCancellation>);
 
 #[tauri::command]
 pub async fn scan_directory(
     path: String,
     window: WebviewWindow,
+    state: State<'_, ScanState>,
 ) -> Result<Vec<String>, String> {
     let path_buf = PathBuf::from(&path);
     let scanner = FileScanner::new();
-    scanner.scan_directory(&path_buf, &window)
+    let cancellation = Arc::clone(&state.0);
+    scanner.scan_directory(&path_buf, &window, cancellation)
+}
+
+#[tauri::command]
+pub async fn cancel_scan(state: State<'_, ScanState>) -> Result<(), String> {
+    state.0.cancel();
+    Ok(())
 }
@@ -4,122 +4,36 @@ use tauri::{
     tray::{TrayIconBuilder, TrayIconEvent},
     Emitter, Manager, WindowEvent,
 };
-use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
 
-#[tauri::command]
-fn exit_app(app_handle: tauri::AppHandle) {
-    app_handle.exit(0);
-}
-
-#[tauri::command]
-fn hide_window(window: tauri::WebviewWindow) {
-    let _ = window.hide();
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct ScanProgress {
-    current: usize,
-    total: usize,
-    current_file: String,
-}
-
-/// Open folder picker dialog and return selected path
-#[tauri::command]
-async fn select_folder(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
-    use tauri_plugin_dialog::DialogExt;
-    
-    let folder = app_handle
-        .dialog()
-        .file()
-        .blocking_pick_folder();
-    
-    match folder {
-        Some(path) => {
-            // Convert FilePath to String
-            let path_str = path.to_string();
-            Ok(Some(path_str))
-        },
-        None => Ok(None),
-    }
-}
+mod commands;
+mod logging;
+mod models;
+mod services;
 
-/// Get list of audio files in a directory (recursive)
-#[tauri::command]
-async fn scan_directory(
-    path: String,
-    window: tauri::WebviewWindow,
-) -> Result<Vec<String>, String> {
-    use std::fs;
-    use std::path::Path;
-    
-    let audio_extensions = vec!["mp3", "flac", "m4a", "aac", "ogg", "opus", "wav", "aiff"];
-    let mut audio_files = Vec::new();
-    
-    fn scan_dir_recursive(
-        dir: &Path,
-        extensions: &[&str],
-        files: &mut Vec<String>,
-        window: &tauri::WebviewWindow,
-    ) -> Result<(), String> {
-        if !dir.is_dir() {
-            return Ok(());
-        }
-        
-        let entries = fs::read_dir(dir).map_err(|e| e.to_string())?;
-        
-        for entry in entries {
-            let entry = entry.map_err(|e| e.to_string())?;
-            let path = entry.path();
-            
-            if path.is_dir() {
-                // Recursively scan subdirectories
-                scan_dir_recursive(&path, extensions, files, window)?;
-            } else if path.is_file() {
-                // Check if file has audio extension
-                if let Some(ext) = path.extension() {
-                    if let Some(ext_str) = ext.to_str() {
-                        if extensions.contains(&ext_str.to_lowercase().as_str()) {
-                            if let Some(path_str) = path.to_str() {
-                                files.push(path_str.to_string());
-                                
-                                // Emit progress event every 10 files
-                                if files.len() % 10 == 0 {
-                                    let _ = window.emit("scan-progress", ScanProgress {
-                                        current: files.len(),
-                                        total: 0, // Unknown until complete
-                                        current_file: path_str.to_string(),
-                                    });
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        
-        Ok(())
-    }
-    
-    let path_buf = PathBuf::from(&path);
-    scan_dir_recursive(&path_buf, &audio_extensions, &mut audio_files, &window)?;
-    
-    // Emit completion event
-    let _ = window.emit("scan-complete", ScanProgress {
-        current: audio_files.len(),
-        total: audio_files.len(),
-        current_file: String::new(),
-    });
-    
-    Ok(audio_files)
-}
+use commands::{
+    analyze_album_loudness, analyze_library_features, analyze_library_loudness,
+    audio_stream_status, cancel_scan, create_aggregate_device, destroy_aggregate_device,
+    exit_app, extract_metadata, finalize_recording, generate_playlist, get_audio_devices,
+    get_default_input_device, get_default_output_device, hide_window, load_graph,
+    pause_recording, recording_status, resume_recording, save_graph, scan_directory,
+    select_folder, set_log_level, set_node_parameter_smoothed, start_aggregate_output_stream,
+    start_duplex_stream, start_input_stream, start_output_stream, start_recording,
+    stop_audio_stream, write_metadata, AudioEngineState, ScanState,
+};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Held for the process lifetime: dropping it stops the non-blocking
+    // writer's background flush thread.
+    let (_log_guard, log_handle) = logging::init_logging();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
+        .manage(ScanState::default())
+        .manage(AudioEngineState::default())
+        .manage(log_handle)
         .setup(|app| {
             let quit_i = MenuItem::with_id(app, "quit", "Quit", true, None::<&str>)?;
             let show_i = MenuItem::with_id(app, "show", "Show Sonántica", true, None::<&str>)?;
@@ -170,7 +84,34 @@ pub fn run() {
             exit_app,
             hide_window,
             select_folder,
-            scan_directory
+            scan_directory,
+            cancel_scan,
+            extract_metadata,
+            write_metadata,
+            get_audio_devices,
+            get_default_input_device,
+            get_default_output_device,
+            create_aggregate_device,
+            destroy_aggregate_device,
+            start_input_stream,
+            start_output_stream,
+            start_duplex_stream,
+            start_aggregate_output_stream,
+            stop_audio_stream,
+            audio_stream_status,
+            set_node_parameter_smoothed,
+            start_recording,
+            pause_recording,
+            resume_recording,
+            finalize_recording,
+            recording_status,
+            analyze_library_features,
+            generate_playlist,
+            analyze_library_loudness,
+            analyze_album_loudness,
+            save_graph,
+            load_graph,
+            set_log_level,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -3,43 +3,82 @@
 
 use std::env;
 use std::fs;
-use std::path::PathBuf;
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_subscriber::{fmt, layer::SubscriberExt, reload, util::SubscriberInitExt, EnvFilter, Registry};
 
-/// Initialize structured logging for the Tauri application
+/// Prefix shared by every rolling log file (`sonantica-desktop.log`,
+/// `sonantica-desktop.log.2026-07-30`, ...)
+const LOG_FILE_PREFIX: &str = "sonantica-desktop.log";
+
+/// Delete rotated log files older than this many days at startup
+const LOG_RETENTION_DAYS: u64 = 14;
+
+/// Hard cap on rotated log files kept, regardless of age, so a clock change
+/// or a very chatty day can't retain an unbounded number of files
+const LOG_RETENTION_MAX_FILES: usize = 30;
+
+/// Handle allowing the log level to be changed at runtime (e.g. from a
+/// settings screen) without restarting the app. Wraps the `EnvFilter`
+/// `reload::Handle` so `set_log_level` only needs to swap the filter in
+/// place; the underlying writers and format layers are unaffected.
+#[derive(Clone)]
+pub struct LogHandle(reload::Handle<EnvFilter, Registry>);
+
+impl LogHandle {
+    /// Replace the active filter directive (e.g. `"debug"`, `"sonantica=trace,info"`)
+    pub fn set_level(&self, directive: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directive).map_err(|e| format!("Invalid log level: {}", e))?;
+        self.0
+            .reload(filter)
+            .map_err(|e| format!("Failed to reload log filter: {}", e))
+    }
+}
+
+/// Initialize structured logging for the Tauri application.
 /// Follows the same pattern as Python services:
 /// - JSON format for production/file logging
 /// - Pretty format for development/console logging
 /// - Configurable via environment variables
-pub fn init_logging() {
+///
+/// Returns the non-blocking writer's `WorkerGuard` (must be held for the
+/// process lifetime - dropping it stops the background flush thread) and a
+/// `LogHandle` for runtime level changes.
+pub fn init_logging() -> (WorkerGuard, LogHandle) {
     // Determine log directory (fallback to user's local data dir)
     let log_dir = get_log_directory();
-    
+
     // Ensure log directory exists
     if let Err(e) = fs::create_dir_all(&log_dir) {
         eprintln!("Failed to create log directory: {}", e);
     }
 
+    prune_old_logs(&log_dir, LOG_FILE_PREFIX, LOG_RETENTION_DAYS, LOG_RETENTION_MAX_FILES);
+
     // Get log level from environment (default: INFO)
     let log_level = env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string());
-    
+
     // Get log format from environment (default: json for production)
     let log_format = env::var("LOG_FORMAT").unwrap_or_else(|_| "json".to_string());
 
-    // Build the filter
+    // Build the filter, wrapped in a reload layer so the level can change at runtime
     let filter = EnvFilter::try_from_default_env()
         .or_else(|_| EnvFilter::try_new(&log_level))
         .unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, reload_handle) = reload::Layer::new(filter);
+
+    // File appender for persistent logs, made non-blocking so log I/O never
+    // happens on the hot path; the returned guard must outlive the app.
+    let log_file = log_dir.join(LOG_FILE_PREFIX);
+    let file_appender = tracing_appender::rolling::daily(&log_dir, LOG_FILE_PREFIX);
+    let (non_blocking_writer, guard) = tracing_appender::non_blocking(file_appender);
 
-    // File appender for persistent logs
-    let log_file = log_dir.join("sonantica-desktop.log");
-    let file_appender = tracing_appender::rolling::daily(log_dir, "sonantica-desktop.log");
-    
     if log_format.to_lowercase() == "json" {
         // JSON format (production)
         let file_layer = fmt::layer()
             .json()
-            .with_writer(file_appender)
+            .with_writer(non_blocking_writer)
             .with_target(true)
             .with_thread_ids(true)
             .with_file(true)
@@ -58,7 +97,7 @@ pub fn init_logging() {
     } else {
         // Pretty format (development)
         let file_layer = fmt::layer()
-            .with_writer(file_appender)
+            .with_writer(non_blocking_writer)
             .with_target(true)
             .with_thread_ids(true)
             .with_file(true)
@@ -82,6 +121,48 @@ pub fn init_logging() {
         log_file = %log_file.display(),
         "Logging initialized"
     );
+
+    (guard, LogHandle(reload_handle))
+}
+
+/// Remove rotated log files (`<prefix>.<date>`) that are either older than
+/// `retention_days` or beyond `max_files` most-recent, whichever is stricter.
+/// The current day's un-rotated `<prefix>` file is never touched.
+fn prune_old_logs(log_dir: &Path, prefix: &str, retention_days: u64, max_files: usize) {
+    let entries = match fs::read_dir(log_dir) {
+        Ok(e) => e,
+        Err(_) => return,
+    };
+
+    let max_age = Duration::from_secs(retention_days * 24 * 60 * 60);
+    let now = SystemTime::now();
+
+    let mut rotated_files: Vec<(PathBuf, SystemTime)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| name.starts_with(prefix) && name != prefix)
+        })
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect();
+
+    rotated_files.sort_by_key(|(_, modified)| std::cmp::Reverse(*modified));
+
+    for (index, (path, modified)) in rotated_files.iter().enumerate() {
+        let too_old = now.duration_since(*modified).unwrap_or(Duration::ZERO) > max_age;
+        let beyond_count = index >= max_files;
+
+        if too_old || beyond_count {
+            if let Err(e) = fs::remove_file(path) {
+                eprintln!("Failed to prune old log file {:?}: {}", path, e);
+            }
+        }
+    }
 }
 
 /// Get the appropriate log directory for the platform
@@ -110,7 +191,7 @@ fn get_log_directory() -> PathBuf {
         if system_log.exists() || fs::create_dir_all(&system_log).is_ok() {
             return system_log;
         }
-        
+
         // Fallback to ~/.local/share/sonantica/logs
         if let Ok(home) = env::var("HOME") {
             return PathBuf::from(home).join(".local").join("share").join("sonantica").join("logs");
@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+
+/// Content-derived descriptor vector for a track, used for similarity search
+/// and automatic "sounds-like" playlist generation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AudioFeatures {
+    /// Content hash of the source file, used as the cache key
+    pub content_hash: String,
+    /// Path the descriptor was extracted from, so callers can map a cache
+    /// entry back to a playable file
+    pub source_path: String,
+    pub tempo_bpm: f32,
+    pub spectral_centroid: f32,
+    pub spectral_rolloff: f32,
+    pub zero_crossing_rate: f32,
+    pub rms_energy: f32,
+    /// 12-bin chroma vector (pitch class energy, C through B)
+    pub chroma: [f32; 12],
+}
+
+impl AudioFeatures {
+    /// Flatten the descriptor into a single vector for distance computation
+    pub fn as_vector(&self) -> Vec<f32> {
+        let mut v = vec![
+            self.tempo_bpm,
+            self.spectral_centroid,
+            self.spectral_rolloff,
+            self.zero_crossing_rate,
+            self.rms_energy,
+        ];
+        v.extend_from_slice(&self.chroma);
+        v
+    }
+}
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use crate::models::AudioFeatures;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AudioMetadata {
     pub title: Option<String>,
@@ -14,6 +16,15 @@ pub struct AudioMetadata {
     pub bitrate: Option<u32>,
     pub sample_rate: Option<u32>,
     pub lyrics: Option<String>,
+    /// Gated integrated loudness from `LoudnessAnalyzer`, in LUFS
+    pub integrated_lufs: Option<f64>,
+    /// ReplayGain-style track gain, in dB, as computed by `LoudnessAnalyzer`
+    pub replaygain_track_gain: Option<f32>,
+    /// Highest absolute sample value seen across all channels, linear scale
+    pub replaygain_track_peak: Option<f32>,
+    /// Content-based descriptor vector for similarity search and
+    /// automatic playlist sequencing, from `MetadataExtractor::extract_features`
+    pub features: Option<AudioFeatures>,
 }
 
 impl Default for AudioMetadata {
@@ -31,6 +42,10 @@ impl Default for AudioMetadata {
             bitrate: None,
             sample_rate: None,
             lyrics: None,
+            integrated_lufs: None,
+            replaygain_track_gain: None,
+            replaygain_track_peak: None,
+            features: None,
         }
     }
 }
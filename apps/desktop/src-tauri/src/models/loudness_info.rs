@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+/// BS.1770 / EBU R128 loudness measurement for a single track, plus the
+/// ReplayGain-style adjustment needed to bring it to a target reference level.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoudnessInfo {
+    pub file_path: String,
+    /// Gated integrated loudness, in LUFS
+    pub integrated_lufs: f64,
+    /// Highest absolute sample value seen across all channels, linear scale
+    pub sample_peak: f32,
+    /// `target_lufs - integrated_lufs`, the gain a player should apply
+    pub track_gain_db: f32,
+}
+
+/// Result of analyzing a whole album in one gated pass, so every track shares
+/// a single album gain instead of being normalized independently.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlbumLoudness {
+    pub tracks: Vec<LoudnessInfo>,
+    pub album_integrated_lufs: f64,
+    pub album_gain_db: f32,
+}
@@ -0,0 +1,11 @@
+pub mod audio_metadata;
+pub mod scan_progress;
+pub mod audio_features;
+pub mod virtual_track;
+pub mod loudness_info;
+
+pub use audio_metadata::AudioMetadata;
+pub use scan_progress::ScanProgress;
+pub use audio_features::AudioFeatures;
+pub use virtual_track::VirtualTrackPath;
+pub use loudness_info::{AlbumLoudness, LoudnessInfo};
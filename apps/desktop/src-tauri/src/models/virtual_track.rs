@@ -0,0 +1,70 @@
+/// Path representation for a single track carved out of a larger audio file
+/// (e.g. one track of an image+CUE rip). Encodes the parent file path plus
+/// the sample range so playback and the compositor can seek straight into
+/// the right region without needing a separate copy of the audio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VirtualTrackPath {
+    pub parent_file: String,
+    pub start_sample: u64,
+    pub end_sample: Option<u64>,
+}
+
+const SEPARATOR: &str = "#frames=";
+
+impl VirtualTrackPath {
+    pub fn new(parent_file: String, start_sample: u64, end_sample: Option<u64>) -> Self {
+        Self { parent_file, start_sample, end_sample }
+    }
+
+    /// Encode as `<parent_file>#frames=<start>-<end|..>`
+    pub fn encode(&self) -> String {
+        match self.end_sample {
+            Some(end) => format!("{}{}{}-{}", self.parent_file, SEPARATOR, self.start_sample, end),
+            None => format!("{}{}{}-..", self.parent_file, SEPARATOR, self.start_sample),
+        }
+    }
+
+    /// Parse a path previously produced by `encode`; returns `None` for a
+    /// plain (non-virtual) file path
+    pub fn parse(path: &str) -> Option<Self> {
+        let (parent_file, range) = path.split_once(SEPARATOR)?;
+        let (start_str, end_str) = range.split_once('-')?;
+
+        let start_sample: u64 = start_str.parse().ok()?;
+        let end_sample = if end_str == ".." {
+            None
+        } else {
+            Some(end_str.parse().ok()?)
+        };
+
+        Some(Self {
+            parent_file: parent_file.to_string(),
+            start_sample,
+            end_sample,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip_with_end() {
+        let vt = VirtualTrackPath::new("/music/album.flac".to_string(), 1000, Some(5000));
+        let encoded = vt.encode();
+        assert_eq!(VirtualTrackPath::parse(&encoded), Some(vt));
+    }
+
+    #[test]
+    fn test_roundtrip_open_ended() {
+        let vt = VirtualTrackPath::new("/music/album.flac".to_string(), 1000, None);
+        let encoded = vt.encode();
+        assert_eq!(VirtualTrackPath::parse(&encoded), Some(vt));
+    }
+
+    #[test]
+    fn test_plain_path_is_not_virtual() {
+        assert_eq!(VirtualTrackPath::parse("/music/song.mp3"), None);
+    }
+}
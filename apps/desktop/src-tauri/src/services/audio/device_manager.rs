@@ -1,6 +1,8 @@
 // Device Manager - Audio Device Enumeration
 // Handles discovery and management of audio input/output devices
 
+use std::collections::HashMap;
+
 use cpal::traits::{DeviceTrait, HostTrait};
 use serde::{Deserialize, Serialize};
 
@@ -26,9 +28,35 @@ pub struct AudioDevice {
     pub host_api: String,
 }
 
+/// A single aggregate member's position in the combined channel map: which
+/// physical device backs a given aggregate channel, and at what local
+/// channel index on that device.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateChannelSlot {
+    pub device_id: String,
+    pub local_channel: u16,
+}
+
+/// Several physical `AudioDevice`s bundled into one clock-synchronized
+/// logical device: member channels are concatenated in member order (device
+/// A channels `0..n`, then device B channels `0..m`, ...), one member is the
+/// master clock, and the rest are resampled to track it (see
+/// `StreamManager`'s aggregate streaming methods).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AggregateDevice {
+    /// The logical device exposed to `list_devices`/the rest of the app
+    pub device: AudioDevice,
+    pub master_id: String,
+    pub members: Vec<AudioDevice>,
+    pub channel_map: Vec<AggregateChannelSlot>,
+}
+
 /// Audio device manager
 pub struct DeviceManager {
     host: cpal::Host,
+    aggregates: HashMap<String, AggregateDevice>,
 }
 
 impl DeviceManager {
@@ -36,10 +64,12 @@ impl DeviceManager {
     pub fn new() -> Self {
         Self {
             host: cpal::default_host(),
+            aggregates: HashMap::new(),
         }
     }
 
-    /// List all available audio devices (inputs and outputs)
+    /// List all available audio devices (inputs and outputs), including any
+    /// aggregate devices created with `create_aggregate`
     #[tracing::instrument(skip(self), name = "audio.list_devices")]
     pub fn list_devices(&self) -> anyhow::Result<Vec<AudioDevice>> {
         tracing::info!("Enumerating audio devices");
@@ -103,6 +133,8 @@ impl DeviceManager {
             tracing::warn!("Failed to enumerate output devices");
         }
 
+        devices.extend(self.aggregates.values().map(|agg| agg.device.clone()));
+
         tracing::info!(device_count = devices.len(), "Audio device enumeration complete");
         Ok(devices)
     }
@@ -199,6 +231,122 @@ impl DeviceManager {
             Ok(None)
         }
     }
+
+    /// Find the underlying cpal device matching a previously-listed `AudioDevice`'s id
+    pub(crate) fn find_device(&self, device: &AudioDevice) -> anyhow::Result<cpal::Device> {
+        let candidates: Box<dyn Iterator<Item = cpal::Device>> = if device.is_input {
+            Box::new(self.host.input_devices()?)
+        } else {
+            Box::new(self.host.output_devices()?)
+        };
+
+        for candidate in candidates {
+            if let Ok(info) = self.get_device_info(&candidate, device.is_input) {
+                if info.id == device.id {
+                    return Ok(candidate);
+                }
+            }
+        }
+
+        anyhow::bail!("Audio device '{}' not found", device.id)
+    }
+
+    /// Bundle `member_ids` into a single aggregate device clocked by
+    /// `master_id`. All members must be the same direction (all-input or
+    /// all-output); the aggregate's channels are the members' channels
+    /// concatenated in `member_ids` order, and it advertises the master's
+    /// sample rates since every other member gets resampled to match it.
+    #[tracing::instrument(skip(self), name = "audio.create_aggregate")]
+    pub fn create_aggregate(
+        &mut self,
+        master_id: &str,
+        member_ids: &[String],
+    ) -> anyhow::Result<AudioDevice> {
+        if !member_ids.iter().any(|id| id == master_id) {
+            anyhow::bail!("Master device '{}' must be one of the aggregate members", master_id);
+        }
+
+        let available = self.list_devices()?;
+        let members: Vec<AudioDevice> = member_ids
+            .iter()
+            .map(|id| {
+                available
+                    .iter()
+                    .find(|d| &d.id == id)
+                    .cloned()
+                    .ok_or_else(|| anyhow::anyhow!("Aggregate member device '{}' not found", id))
+            })
+            .collect::<anyhow::Result<_>>()?;
+
+        let is_input = members[0].is_input;
+        if members.iter().any(|d| d.is_input != is_input) {
+            anyhow::bail!("Aggregate members must all be input devices or all be output devices");
+        }
+
+        let master = members
+            .iter()
+            .find(|d| d.id == master_id)
+            .expect("master_id was checked against member_ids above")
+            .clone();
+
+        let mut channel_map = Vec::new();
+        let mut total_channels: u16 = 0;
+        for member in &members {
+            let channels = if is_input { member.input_channels } else { member.output_channels }
+                .unwrap_or(0);
+            for local_channel in 0..channels {
+                channel_map.push(AggregateChannelSlot {
+                    device_id: member.id.clone(),
+                    local_channel,
+                });
+            }
+            total_channels += channels;
+        }
+
+        let id = format!("aggregate_{}", member_ids.join("+"));
+        let name = format!(
+            "Aggregate ({})",
+            members.iter().map(|d| d.name.as_str()).collect::<Vec<_>>().join(" + ")
+        );
+
+        let device = AudioDevice {
+            id: id.clone(),
+            name,
+            is_input,
+            is_default: false,
+            input_channels: if is_input { Some(total_channels) } else { None },
+            output_channels: if is_input { None } else { Some(total_channels) },
+            sample_rates: master.sample_rates.clone(),
+            host_api: "Aggregate".to_string(),
+        };
+
+        self.aggregates.insert(
+            id.clone(),
+            AggregateDevice {
+                device: device.clone(),
+                master_id: master_id.to_string(),
+                members,
+                channel_map,
+            },
+        );
+
+        tracing::info!(aggregate_id = %id, channels = total_channels, "Created aggregate device");
+        Ok(device)
+    }
+
+    /// Tear down a previously created aggregate device. Does not affect the
+    /// underlying physical devices.
+    pub fn destroy_aggregate(&mut self, id: &str) -> anyhow::Result<()> {
+        self.aggregates
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| anyhow::anyhow!("Aggregate device '{}' not found", id))
+    }
+
+    /// Look up a previously created aggregate device's definition
+    pub fn aggregate(&self, id: &str) -> Option<&AggregateDevice> {
+        self.aggregates.get(id)
+    }
 }
 
 impl Default for DeviceManager {
@@ -241,6 +389,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_create_aggregate_rejects_master_not_in_members() {
+        let mut manager = DeviceManager::new();
+        let result = manager.create_aggregate(
+            "missing_device",
+            &["a".to_string(), "b".to_string()],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_create_and_destroy_aggregate() {
+        let mut manager = DeviceManager::new();
+        let devices = manager.list_devices().expect("Failed to list devices");
+        let input_ids: Vec<String> = devices
+            .iter()
+            .filter(|d| d.is_input)
+            .map(|d| d.id.clone())
+            .collect();
+
+        if input_ids.len() < 2 {
+            // Not enough real input devices on this machine to exercise
+            // aggregation end to end
+            return;
+        }
+
+        let members = input_ids[..2].to_vec();
+        let aggregate = manager.create_aggregate(&members[0], &members).unwrap();
+        assert!(aggregate.is_input);
+        assert_eq!(
+            aggregate.input_channels,
+            devices.iter()
+                .filter(|d| members.contains(&d.id))
+                .map(|d| d.input_channels.unwrap_or(0))
+                .reduce(|a, b| a + b)
+        );
+
+        let listed = manager.list_devices().unwrap();
+        assert!(listed.iter().any(|d| d.id == aggregate.id));
+
+        manager.destroy_aggregate(&aggregate.id).unwrap();
+        let listed_after = manager.list_devices().unwrap();
+        assert!(!listed_after.iter().any(|d| d.id == aggregate.id));
+    }
+
     #[test]
     fn test_default_devices() {
         let manager = DeviceManager::new();
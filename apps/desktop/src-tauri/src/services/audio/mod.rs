@@ -3,10 +3,14 @@
 //
 // Architecture:
 // - DeviceManager: Enumerate and manage audio devices
-// - StreamManager: Handle audio stream lifecycle (future)
+// - StreamManager: Handle audio stream lifecycle
+// - Recorder: Capture audio to disk
 // - Patchbay: Route audio between devices (future)
-// - Recorder: Capture audio to disk (future)
 
 pub mod device_manager;
+pub mod stream_manager;
+pub mod recorder;
 
 pub use device_manager::{AudioDevice, DeviceManager};
+pub use stream_manager::{StreamManager, StreamStatus};
+pub use recorder::{ParameterSnapshot, Recorder, RecordingMetadata, RecordingStatus};
@@ -0,0 +1,356 @@
+// Recorder - Persists AudioGraph output to disk
+// Captures processed audio blocks to a WAV file (or, behind the `hdf5`
+// feature, an HDF5 dataset) without blocking the real-time processing path:
+// samples are pushed into a ring buffer and a dedicated writer thread drains
+// it and appends to disk.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use ringbuf::traits::{Consumer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use serde::{Deserialize, Serialize};
+
+/// Matches the block size the rest of the audio engine (see
+/// `stream_manager::BLOCK_SIZE_FRAMES`) drives the graph at
+const BLOCK_SIZE_FRAMES: usize = 512;
+
+/// Ring buffer capacity, in blocks, buffered between the real-time path
+/// pushing processed samples and the writer thread flushing them to disk
+const RING_BUFFER_BLOCKS: usize = 32;
+
+/// How often the writer thread wakes up to drain the ring buffer when it
+/// isn't already full of work
+const WRITER_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Number of frames per HDF5 chunk, so long measurements append cheaply
+/// instead of rewriting the whole dataset
+#[cfg(feature = "hdf5")]
+const HDF5_CHUNK_FRAMES: usize = 4096;
+
+/// A node's parameters at the moment recording started, so a take can always
+/// be traced back to the settings that produced it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterSnapshot {
+    pub node_id: String,
+    pub parameters: HashMap<String, f32>,
+}
+
+/// Sidecar metadata written alongside the recording (`<path>.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingMetadata {
+    pub sample_rate: u32,
+    pub channels: usize,
+    pub channel_layout: Option<String>,
+    /// Unix epoch milliseconds when recording started
+    pub started_at_unix_ms: u64,
+    pub parameters: Vec<ParameterSnapshot>,
+}
+
+/// Running health/progress snapshot, for a UI to show a timer and catch
+/// overruns
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordingStatus {
+    pub recording: bool,
+    pub paused: bool,
+    pub frames_recorded: u64,
+    pub elapsed: Duration,
+    /// Number of samples dropped because the ring buffer was full
+    pub overruns: u64,
+}
+
+/// Taps an `AudioGraph`'s output (or any other block source) and streams it
+/// to disk. `push` is safe to call from the real-time processing path: it
+/// never blocks or allocates, it just tries to enqueue samples and counts an
+/// overrun if the writer thread has fallen behind.
+pub struct Recorder {
+    producer: Option<HeapProd<f32>>,
+    writer: Option<JoinHandle<()>>,
+    stop: Arc<AtomicBool>,
+    paused: Arc<AtomicBool>,
+    frames_recorded: Arc<AtomicU64>,
+    overruns: Arc<AtomicU64>,
+    sample_rate: u32,
+    channels: usize,
+    started_at: Option<Instant>,
+}
+
+impl Recorder {
+    pub fn new() -> Self {
+        Self {
+            producer: None,
+            writer: None,
+            stop: Arc::new(AtomicBool::new(false)),
+            paused: Arc::new(AtomicBool::new(false)),
+            frames_recorded: Arc::new(AtomicU64::new(0)),
+            overruns: Arc::new(AtomicU64::new(0)),
+            sample_rate: 0,
+            channels: 0,
+            started_at: None,
+        }
+    }
+
+    /// Begin recording to `path`, writing a sidecar `RecordingMetadata` JSON
+    /// file immediately so the parameter snapshot reflects settings at the
+    /// moment recording actually started.
+    pub fn start(
+        &mut self,
+        path: impl Into<PathBuf>,
+        sample_rate: u32,
+        channels: usize,
+        parameters: Vec<ParameterSnapshot>,
+    ) -> anyhow::Result<()> {
+        if self.writer.is_some() {
+            anyhow::bail!("Recorder is already running");
+        }
+
+        let path = path.into();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let metadata = RecordingMetadata {
+            sample_rate,
+            channels,
+            channel_layout: None,
+            started_at_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+            parameters,
+        };
+        fs::write(metadata_path(&path), serde_json::to_string_pretty(&metadata)?)?;
+
+        let ring = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * channels * RING_BUFFER_BLOCKS);
+        let (producer, consumer) = ring.split();
+
+        self.stop.store(false, Ordering::Relaxed);
+        self.paused.store(false, Ordering::Relaxed);
+        self.frames_recorded.store(0, Ordering::Relaxed);
+        self.overruns.store(0, Ordering::Relaxed);
+
+        let writer = spawn_writer(
+            path,
+            channels,
+            sample_rate,
+            consumer,
+            Arc::clone(&self.stop),
+            Arc::clone(&self.frames_recorded),
+        );
+
+        self.producer = Some(producer);
+        self.writer = Some(writer);
+        self.sample_rate = sample_rate;
+        self.channels = channels;
+        self.started_at = Some(Instant::now());
+
+        Ok(())
+    }
+
+    /// Push interleaved samples from the real-time processing path. Never
+    /// blocks or allocates: samples that don't fit are dropped and counted
+    /// as an overrun. Samples pushed while paused are dropped too, but don't
+    /// count as one.
+    pub fn push(&mut self, samples: &[f32]) {
+        if self.paused.load(Ordering::Relaxed) {
+            return;
+        }
+
+        if let Some(producer) = &mut self.producer {
+            for &sample in samples {
+                if producer.try_push(sample).is_err() {
+                    self.overruns.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    }
+
+    /// Pause without stopping the writer thread: incoming samples are
+    /// dropped, not written, until `resume` is called
+    pub fn pause(&mut self) {
+        self.paused.store(true, Ordering::Relaxed);
+    }
+
+    pub fn resume(&mut self) {
+        self.paused.store(false, Ordering::Relaxed);
+    }
+
+    /// Stop recording, flush the writer thread, and finalize the file header
+    /// (WAV) or dataset (HDF5). Returns the final status.
+    pub fn finalize(&mut self) -> anyhow::Result<RecordingStatus> {
+        self.stop.store(true, Ordering::Relaxed);
+        self.producer = None;
+
+        if let Some(writer) = self.writer.take() {
+            writer
+                .join()
+                .map_err(|_| anyhow::anyhow!("Recorder writer thread panicked"))?;
+        }
+
+        let status = self.status();
+        self.started_at = None;
+        Ok(status)
+    }
+
+    pub fn frames_recorded(&self) -> u64 {
+        self.frames_recorded.load(Ordering::Relaxed)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.map(|t| t.elapsed()).unwrap_or_default()
+    }
+
+    /// Current health/progress snapshot, for a UI to show a running timer
+    pub fn status(&self) -> RecordingStatus {
+        RecordingStatus {
+            recording: self.writer.is_some(),
+            paused: self.paused.load(Ordering::Relaxed),
+            frames_recorded: self.frames_recorded(),
+            elapsed: self.elapsed(),
+            overruns: self.overruns.load(Ordering::Relaxed),
+        }
+    }
+}
+
+impl Default for Recorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn metadata_path(recording_path: &Path) -> PathBuf {
+    let mut path = recording_path.as_os_str().to_owned();
+    path.push(".json");
+    PathBuf::from(path)
+}
+
+#[cfg(not(feature = "hdf5"))]
+fn spawn_writer(
+    path: PathBuf,
+    channels: usize,
+    sample_rate: u32,
+    mut consumer: HeapCons<f32>,
+    stop: Arc<AtomicBool>,
+    frames_recorded: Arc<AtomicU64>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let spec = hound::WavSpec {
+            channels: channels as u16,
+            sample_rate,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+
+        let mut writer = match hound::WavWriter::create(&path, spec) {
+            Ok(w) => w,
+            Err(err) => {
+                tracing::error!(error = %err, path = %path.display(), "Failed to open WAV writer");
+                return;
+            }
+        };
+
+        loop {
+            let mut drained = false;
+            for sample in consumer.pop_iter() {
+                if let Err(err) = writer.write_sample(sample) {
+                    tracing::error!(error = %err, "Failed to write recorded sample");
+                }
+                drained = true;
+            }
+
+            if drained {
+                frames_recorded.store(
+                    (writer.len() as u64) / channels as u64,
+                    Ordering::Relaxed,
+                );
+            }
+
+            if stop.load(Ordering::Relaxed) && consumer.is_empty() {
+                break;
+            }
+
+            std::thread::sleep(WRITER_POLL_INTERVAL);
+        }
+
+        if let Err(err) = writer.finalize() {
+            tracing::error!(error = %err, "Failed to finalize WAV file");
+        }
+    })
+}
+
+#[cfg(feature = "hdf5")]
+fn spawn_writer(
+    path: PathBuf,
+    channels: usize,
+    sample_rate: u32,
+    mut consumer: HeapCons<f32>,
+    stop: Arc<AtomicBool>,
+    frames_recorded: Arc<AtomicU64>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let file = match hdf5::File::create(&path) {
+            Ok(f) => f,
+            Err(err) => {
+                tracing::error!(error = %err, path = %path.display(), "Failed to create HDF5 file");
+                return;
+            }
+        };
+
+        if let Err(err) = file.new_attr::<u32>().create("sample_rate").and_then(|a| a.write_scalar(&sample_rate)) {
+            tracing::error!(error = %err, "Failed to write sample_rate attribute");
+        }
+
+        // Resizable dataset, chunked along the frame axis so long recordings
+        // append cheaply instead of rewriting the whole dataset each flush.
+        let dataset = match file
+            .new_dataset::<f32>()
+            .chunk((HDF5_CHUNK_FRAMES, channels))
+            .shape((0.., channels))
+            .create("samples")
+        {
+            Ok(d) => d,
+            Err(err) => {
+                tracing::error!(error = %err, "Failed to create HDF5 dataset");
+                return;
+            }
+        };
+
+        let mut pending: Vec<f32> = Vec::with_capacity(HDF5_CHUNK_FRAMES * channels);
+        let mut frames_written = 0usize;
+
+        loop {
+            pending.extend(consumer.pop_iter());
+
+            let complete_frames = pending.len() / channels;
+            if complete_frames > 0 {
+                let take = complete_frames * channels;
+                let chunk = ndarray::Array2::from_shape_vec(
+                    (complete_frames, channels),
+                    pending[..take].to_vec(),
+                )
+                .expect("frame-aligned sample chunk");
+
+                if dataset.resize((frames_written + complete_frames, channels)).is_ok() {
+                    let _ = dataset.write_slice(&chunk, (frames_written.., ..));
+                    frames_written += complete_frames;
+                    frames_recorded.store(frames_written as u64, Ordering::Relaxed);
+                }
+
+                pending.drain(..take);
+            }
+
+            if stop.load(Ordering::Relaxed) && consumer.is_empty() && pending.is_empty() {
+                break;
+            }
+
+            std::thread::sleep(WRITER_POLL_INTERVAL);
+        }
+    })
+}
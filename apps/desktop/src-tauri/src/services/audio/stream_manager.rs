@@ -0,0 +1,524 @@
+// Stream Manager - Real-time audio I/O
+// Drives an `AudioGraph` block-by-block from live cpal input/output streams
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use cpal::traits::{DeviceTrait, StreamTrait};
+use espectro::AudioBuffer;
+use espectro::AudioGraph;
+use ringbuf::traits::{Consumer, Producer, Split};
+use ringbuf::{HeapCons, HeapProd, HeapRb};
+use serde::{Deserialize, Serialize};
+
+use super::device_manager::{AudioDevice, DeviceManager};
+use super::recorder::Recorder;
+
+/// Block size (in frames) the graph is driven at, regardless of the
+/// variable-size buffers cpal hands to the callback
+const BLOCK_SIZE_FRAMES: usize = 512;
+
+/// Ring buffer capacity, in blocks, buffered between the callback and the
+/// graph-processing step
+const RING_BUFFER_BLOCKS: usize = 8;
+
+/// Health snapshot of a running (or stopped) stream
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamStatus {
+    /// Whether a stream is currently playing
+    pub running: bool,
+
+    /// Number of output samples the callback had to fill with silence
+    /// because no processed sample was ready in time
+    pub underruns: u64,
+
+    /// Number of times incoming samples had to be dropped because the ring
+    /// buffer was full
+    pub overruns: u64,
+}
+
+/// Owns the lifetime of live cpal input/output streams and drives an
+/// `AudioGraph` from them.
+///
+/// cpal delivers variable-size buffers on its own high-priority callback
+/// thread, while the graph processes fixed-size blocks. A lock-free SPSC
+/// ring buffer sits between the two: the callback pushes raw samples in (or
+/// pops processed samples out), and drains/fills full `BLOCK_SIZE_FRAMES`
+/// blocks through `AudioGraph::process` as they become available.
+pub struct StreamManager {
+    graph: Arc<Mutex<AudioGraph>>,
+    /// Tapped with whatever block `AudioGraph::process` just produced, so a
+    /// recording started on this handle captures exactly what's live on the
+    /// stream without the stream and recorder needing to know about each
+    /// other beyond it.
+    recorder: Arc<Mutex<Recorder>>,
+    input_stream: Option<cpal::Stream>,
+    output_stream: Option<cpal::Stream>,
+    /// Per-member streams for an active aggregate device (see
+    /// `start_aggregate_output`); kept alive for as long as the aggregate is
+    /// running, torn down together by `stop`.
+    aggregate_streams: Vec<cpal::Stream>,
+    underruns: Arc<AtomicU64>,
+    overruns: Arc<AtomicU64>,
+}
+
+impl StreamManager {
+    /// Create a new stream manager driving `graph`, tapping every processed
+    /// block to `recorder`
+    pub fn new(graph: Arc<Mutex<AudioGraph>>, recorder: Arc<Mutex<Recorder>>) -> Self {
+        Self {
+            graph,
+            recorder,
+            input_stream: None,
+            output_stream: None,
+            aggregate_streams: Vec::new(),
+            underruns: Arc::new(AtomicU64::new(0)),
+            overruns: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Open an input-only stream: captures `device`, runs each full block
+    /// through the graph (e.g. for metering or recording nodes), and
+    /// discards the processed output.
+    #[tracing::instrument(skip(self, devices), name = "audio.start_input")]
+    pub fn start_input(
+        &mut self,
+        devices: &DeviceManager,
+        device: &AudioDevice,
+    ) -> anyhow::Result<StreamStatus> {
+        let cpal_device = devices.find_device(device)?;
+        let config = cpal_device.default_input_config()?;
+        let channels = config.channels() as usize;
+        let sample_rate = config.sample_rate().0;
+
+        let ring = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * channels * RING_BUFFER_BLOCKS);
+        let (mut producer, mut consumer) = ring.split();
+
+        let graph = Arc::clone(&self.graph);
+        let recorder = Arc::clone(&self.recorder);
+        let overruns = Arc::clone(&self.overruns);
+        let mut block = Vec::with_capacity(BLOCK_SIZE_FRAMES * channels);
+
+        let stream = cpal_device.build_input_stream(
+            &config.into(),
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                for &sample in data {
+                    if producer.try_push(sample).is_err() {
+                        overruns.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+
+                while consumer.occupied_len() >= BLOCK_SIZE_FRAMES * channels {
+                    block.clear();
+                    block.extend(consumer.pop_iter().take(BLOCK_SIZE_FRAMES * channels));
+
+                    let input = AudioBuffer {
+                        channels,
+                        sample_rate,
+                        samples: block.clone(),
+                    };
+
+                    if let Ok(mut graph) = graph.lock() {
+                        if let Ok(processed) = graph.process(input) {
+                            if let Ok(mut recorder) = recorder.lock() {
+                                recorder.push(&processed.samples);
+                            }
+                        }
+                    }
+                }
+            },
+            |err| tracing::error!(error = %err, "Input stream error"),
+            None,
+        )?;
+
+        stream.play()?;
+        self.input_stream = Some(stream);
+
+        Ok(self.status())
+    }
+
+    /// Open an output-only stream: pulls the next block through the graph
+    /// (fed with silence, since there is no live input) and plays the
+    /// result out of `device`. Counts one underrun for every output sample
+    /// the ring buffer can't supply (that sample is played as silence).
+    #[tracing::instrument(skip(self, devices), name = "audio.start_output")]
+    pub fn start_output(
+        &mut self,
+        devices: &DeviceManager,
+        device: &AudioDevice,
+    ) -> anyhow::Result<StreamStatus> {
+        let cpal_device = devices.find_device(device)?;
+        let config = cpal_device.default_output_config()?;
+        let channels = config.channels() as usize;
+        let sample_rate = config.sample_rate().0;
+
+        let ring = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * channels * RING_BUFFER_BLOCKS);
+        let (mut producer, mut consumer) = ring.split();
+
+        let graph = Arc::clone(&self.graph);
+        let recorder = Arc::clone(&self.recorder);
+        let underruns = Arc::clone(&self.underruns);
+
+        let stream = cpal_device.build_output_stream(
+            &config.into(),
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                while producer.vacant_len() >= BLOCK_SIZE_FRAMES * channels {
+                    let silence = AudioBuffer::silence(channels, sample_rate, BLOCK_SIZE_FRAMES);
+
+                    let processed = match graph.lock() {
+                        Ok(mut graph) => graph
+                            .process(silence)
+                            .unwrap_or_else(|_| silence_buffer(channels, sample_rate)),
+                        Err(_) => silence_buffer(channels, sample_rate),
+                    };
+
+                    if let Ok(mut recorder) = recorder.lock() {
+                        recorder.push(&processed.samples);
+                    }
+
+                    producer.push_slice(&processed.samples);
+                }
+
+                for sample in data.iter_mut() {
+                    *sample = consumer.try_pop().unwrap_or_else(|| {
+                        underruns.fetch_add(1, Ordering::Relaxed);
+                        0.0
+                    });
+                }
+            },
+            |err| tracing::error!(error = %err, "Output stream error"),
+            None,
+        )?;
+
+        stream.play()?;
+        self.output_stream = Some(stream);
+
+        Ok(self.status())
+    }
+
+    /// Open both an input and output stream on the same pair of devices,
+    /// routing the captured input through the graph and playing its output.
+    #[tracing::instrument(skip(self, devices), name = "audio.start_duplex")]
+    pub fn start_duplex(
+        &mut self,
+        devices: &DeviceManager,
+        input: &AudioDevice,
+        output: &AudioDevice,
+    ) -> anyhow::Result<StreamStatus> {
+        let input_cpal = devices.find_device(input)?;
+        let input_config = input_cpal.default_input_config()?;
+        let input_channels = input_config.channels() as usize;
+        let sample_rate = input_config.sample_rate().0;
+
+        let output_cpal = devices.find_device(output)?;
+        let output_config = output_cpal.default_output_config()?;
+        let output_channels = output_config.channels() as usize;
+
+        let captured = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * input_channels * RING_BUFFER_BLOCKS);
+        let (mut captured_tx, mut captured_rx) = captured.split();
+
+        let processed = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * output_channels * RING_BUFFER_BLOCKS);
+        let (mut processed_tx, mut processed_rx) = processed.split();
+
+        let graph = Arc::clone(&self.graph);
+        let recorder = Arc::clone(&self.recorder);
+        let overruns = Arc::clone(&self.overruns);
+        let mut block = Vec::with_capacity(BLOCK_SIZE_FRAMES * input_channels);
+
+        let input_stream = input_cpal.build_input_stream(
+            &input_config.into(),
+            move |data: &[f32], _: &cpal::InputCallbackInfo| {
+                for &sample in data {
+                    if captured_tx.try_push(sample).is_err() {
+                        overruns.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+
+                while captured_rx.occupied_len() >= BLOCK_SIZE_FRAMES * input_channels {
+                    block.clear();
+                    block.extend(captured_rx.pop_iter().take(BLOCK_SIZE_FRAMES * input_channels));
+
+                    let input_buffer = AudioBuffer {
+                        channels: input_channels,
+                        sample_rate,
+                        samples: block.clone(),
+                    };
+
+                    let output_buffer = match graph.lock() {
+                        Ok(mut graph) => graph
+                            .process(input_buffer)
+                            .unwrap_or_else(|_| AudioBuffer::silence(output_channels, sample_rate, BLOCK_SIZE_FRAMES)),
+                        Err(_) => AudioBuffer::silence(output_channels, sample_rate, BLOCK_SIZE_FRAMES),
+                    };
+
+                    if let Ok(mut recorder) = recorder.lock() {
+                        recorder.push(&output_buffer.samples);
+                    }
+
+                    processed_tx.push_slice(&output_buffer.samples);
+                }
+            },
+            |err| tracing::error!(error = %err, "Duplex input stream error"),
+            None,
+        )?;
+
+        let underruns = Arc::clone(&self.underruns);
+        let output_stream = output_cpal.build_output_stream(
+            &output_config.into(),
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                for sample in data.iter_mut() {
+                    *sample = processed_rx.try_pop().unwrap_or_else(|| {
+                        underruns.fetch_add(1, Ordering::Relaxed);
+                        0.0
+                    });
+                }
+            },
+            |err| tracing::error!(error = %err, "Duplex output stream error"),
+            None,
+        )?;
+
+        input_stream.play()?;
+        output_stream.play()?;
+
+        self.input_stream = Some(input_stream);
+        self.output_stream = Some(output_stream);
+
+        Ok(self.status())
+    }
+
+    /// Open an aggregate device for output: the master member's callback
+    /// drives `AudioGraph::process` for the whole aggregate and fans each
+    /// member's channel slice out to a per-member ring buffer; every member
+    /// (including the master) just drains its own buffer into its own cpal
+    /// stream. Non-master members resample what they drain using a ratio
+    /// that slowly adapts to their buffer's observed fill level, so their
+    /// clock tracks the master's without an audible pitch wobble.
+    #[tracing::instrument(skip(self, devices), name = "audio.start_aggregate_output")]
+    pub fn start_aggregate_output(
+        &mut self,
+        devices: &DeviceManager,
+        aggregate_id: &str,
+    ) -> anyhow::Result<StreamStatus> {
+        let aggregate = devices
+            .aggregate(aggregate_id)
+            .ok_or_else(|| anyhow::anyhow!("Aggregate device '{}' not found", aggregate_id))?
+            .clone();
+
+        let master_device = aggregate
+            .members
+            .iter()
+            .find(|m| m.id == aggregate.master_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Aggregate master device not found among its members"))?;
+
+        let master_cpal = devices.find_device(&master_device)?;
+        let master_config = master_cpal.default_output_config()?;
+        let sample_rate = master_config.sample_rate().0;
+        let total_channels = aggregate.channel_map.len();
+
+        // One ring buffer per member. The master's callback is the only one
+        // that ever calls `AudioGraph::process`; every member just drains
+        // its own channel slice out of its own buffer.
+        let mut producers: Vec<(usize, HeapProd<f32>)> = Vec::with_capacity(aggregate.members.len());
+        let mut master_consumer: Option<HeapCons<f32>> = None;
+        let mut slave_consumers: Vec<(cpal::Device, usize, HeapCons<f32>)> = Vec::new();
+
+        for member in &aggregate.members {
+            let channels = aggregate
+                .channel_map
+                .iter()
+                .filter(|slot| slot.device_id == member.id)
+                .count()
+                .max(1);
+
+            let ring = HeapRb::<f32>::new(BLOCK_SIZE_FRAMES * channels * RING_BUFFER_BLOCKS);
+            let (producer, consumer) = ring.split();
+            producers.push((channels, producer));
+
+            if member.id == aggregate.master_id {
+                master_consumer = Some(consumer);
+            } else {
+                let member_cpal = devices.find_device(member)?;
+                slave_consumers.push((member_cpal, channels, consumer));
+            }
+        }
+        let mut master_consumer = master_consumer.expect("master is one of its own members");
+        let master_index = aggregate
+            .members
+            .iter()
+            .position(|m| m.id == aggregate.master_id)
+            .expect("master is one of its own members");
+
+        let graph = Arc::clone(&self.graph);
+        let overruns = Arc::clone(&self.overruns);
+        let underruns = Arc::clone(&self.underruns);
+        let recorder = Arc::clone(&self.recorder);
+
+        let master_stream = master_cpal.build_output_stream(
+            &master_config.into(),
+            move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+                while producers[master_index].1.vacant_len() / producers[master_index].0 >= BLOCK_SIZE_FRAMES {
+                    let silence = AudioBuffer::silence(total_channels, sample_rate, BLOCK_SIZE_FRAMES);
+                    let processed = match graph.lock() {
+                        Ok(mut graph) => graph
+                            .process(silence)
+                            .unwrap_or_else(|_| AudioBuffer::silence(total_channels, sample_rate, BLOCK_SIZE_FRAMES)),
+                        Err(_) => AudioBuffer::silence(total_channels, sample_rate, BLOCK_SIZE_FRAMES),
+                    };
+
+                    if let Ok(mut recorder) = recorder.lock() {
+                        recorder.push(&processed.samples);
+                    }
+
+                    let mut offset = 0usize;
+                    for (channels, producer) in producers.iter_mut() {
+                        for frame in processed.samples.chunks(total_channels) {
+                            for ch in 0..*channels {
+                                if producer.try_push(frame[offset + ch]).is_err() {
+                                    overruns.fetch_add(1, Ordering::Relaxed);
+                                }
+                            }
+                        }
+                        offset += *channels;
+                    }
+                }
+
+                for sample in data.iter_mut() {
+                    *sample = master_consumer.try_pop().unwrap_or_else(|| {
+                        underruns.fetch_add(1, Ordering::Relaxed);
+                        0.0
+                    });
+                }
+            },
+            |err| tracing::error!(error = %err, "Aggregate master stream error"),
+            None,
+        )?;
+        master_stream.play()?;
+        self.aggregate_streams.push(master_stream);
+
+        for (member_cpal, channels, consumer) in slave_consumers {
+            let config = member_cpal.default_output_config()?;
+            let underruns = Arc::clone(&self.underruns);
+
+            let stream = build_drift_compensated_output_stream(
+                &member_cpal,
+                &config.into(),
+                channels,
+                consumer,
+                underruns,
+            )?;
+            stream.play()?;
+            self.aggregate_streams.push(stream);
+        }
+
+        Ok(self.status())
+    }
+
+    /// Stop and drop any running streams
+    pub fn stop(&mut self) -> StreamStatus {
+        self.input_stream = None;
+        self.output_stream = None;
+        self.aggregate_streams.clear();
+        self.status()
+    }
+
+    /// Current health snapshot, including whether a stream is live
+    pub fn status(&self) -> StreamStatus {
+        StreamStatus {
+            running: self.input_stream.is_some()
+                || self.output_stream.is_some()
+                || !self.aggregate_streams.is_empty(),
+            underruns: self.underruns.load(Ordering::Relaxed),
+            overruns: self.overruns.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Slowly-adapting clock-drift compensator for a non-master aggregate
+/// member.
+///
+/// Rather than measure drift directly, this watches how full the member's
+/// ring buffer stays relative to a target (half-full) level: a buffer that
+/// trends fuller means this device is draining slower than the master is
+/// filling it (its clock runs slow relative to the master), so played-back
+/// audio needs to be stretched out slightly faster to catch up, and vice
+/// versa. The correction is nudged gradually so it never produces an
+/// audible pitch wobble.
+struct DriftCompensator {
+    ratio: f32,
+    target_fill: usize,
+}
+
+impl DriftCompensator {
+    const ADAPT_RATE: f32 = 0.0005;
+    const MAX_CORRECTION: f32 = 0.02;
+
+    fn new(capacity: usize) -> Self {
+        Self {
+            ratio: 1.0,
+            target_fill: capacity / 2,
+        }
+    }
+
+    /// Nudge the compensation ratio toward reality based on the latest
+    /// observed occupied length, and return it
+    fn observe(&mut self, occupied: usize) -> f32 {
+        let error = occupied as f32 - self.target_fill as f32;
+        let error_ratio = error / self.target_fill.max(1) as f32;
+        self.ratio = (self.ratio + error_ratio * Self::ADAPT_RATE)
+            .clamp(1.0 - Self::MAX_CORRECTION, 1.0 + Self::MAX_CORRECTION);
+        self.ratio
+    }
+}
+
+/// Build an output stream for a non-master aggregate member: drains
+/// `consumer` at a linearly-interpolated rate governed by a
+/// `DriftCompensator`, so its playback speed slowly tracks the master's
+/// clock instead of drifting out of sync over a long recording.
+fn build_drift_compensated_output_stream(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    channels: usize,
+    mut consumer: HeapCons<f32>,
+    underruns: Arc<AtomicU64>,
+) -> anyhow::Result<cpal::Stream> {
+    let mut drift = DriftCompensator::new(BLOCK_SIZE_FRAMES * channels * RING_BUFFER_BLOCKS);
+    let mut cur_frame = vec![0.0f32; channels];
+    let mut next_frame = vec![0.0f32; channels];
+    let mut frac = 1.0f32; // force an initial frame fetch before any output
+
+    let stream = device.build_output_stream(
+        config,
+        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| {
+            let ratio = drift.observe(consumer.occupied_len());
+
+            for frame in data.chunks_mut(channels) {
+                while frac >= 1.0 {
+                    cur_frame.copy_from_slice(&next_frame);
+                    for sample in next_frame.iter_mut() {
+                        *sample = consumer.try_pop().unwrap_or_else(|| {
+                            underruns.fetch_add(1, Ordering::Relaxed);
+                            0.0
+                        });
+                    }
+                    frac -= 1.0;
+                }
+
+                for (ch, out) in frame.iter_mut().enumerate() {
+                    *out = cur_frame[ch] + (next_frame[ch] - cur_frame[ch]) * frac;
+                }
+
+                frac += ratio;
+            }
+        },
+        |err| tracing::error!(error = %err, "Aggregate member stream error"),
+        None,
+    )?;
+
+    Ok(stream)
+}
+
+fn silence_buffer(channels: usize, sample_rate: u32) -> AudioBuffer {
+    AudioBuffer::silence(channels, sample_rate, BLOCK_SIZE_FRAMES)
+}
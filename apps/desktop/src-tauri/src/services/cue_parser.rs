@@ -0,0 +1,163 @@
+use std::fs;
+use std::path::Path;
+
+use crate::models::AudioMetadata;
+
+/// Frames per second used by the Red Book CD timecode (MM:SS:FF)
+const FRAMES_PER_SECOND: u32 = 75;
+
+/// A single `TRACK` entry parsed from a CUE sheet, before duration is known
+struct CueTrackEntry {
+    track_number: u32,
+    title: Option<String>,
+    performer: Option<String>,
+    /// INDEX 01 position, in frames from the start of the referenced FILE
+    start_frame: u64,
+}
+
+/// Parses CUE sheets describing track boundaries inside a single audio file
+/// (common for lossless album rips distributed as one FLAC/WAV + a `.cue`).
+pub struct CueParser;
+
+impl CueParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse a `.cue` file, returning per-track metadata with `title`,
+    /// `artist`, `track_number` and `duration` (in seconds), together with
+    /// each track's start sample and end sample (`None` for the final
+    /// track), derived from consecutive `INDEX 01` timecodes and the
+    /// referenced file's total duration for the final track.
+    pub fn parse(&self, cue_path: &Path, audio_file_sample_rate: u32, total_duration_secs: f64) -> Result<Vec<(AudioMetadata, u64, Option<u64>)>, String> {
+        let contents = fs::read_to_string(cue_path)
+            .map_err(|e| format!("Failed to read CUE sheet: {}", e))?;
+
+        let mut album: Option<String> = None;
+        let mut album_performer: Option<String> = None;
+        let mut entries: Vec<CueTrackEntry> = Vec::new();
+        let mut current: Option<CueTrackEntry> = None;
+
+        for raw_line in contents.lines() {
+            let line = raw_line.trim();
+
+            if let Some(rest) = line.strip_prefix("TITLE ") {
+                let title = Self::unquote(rest);
+                match &mut current {
+                    Some(track) => track.title = Some(title),
+                    None => album = Some(title),
+                }
+            } else if let Some(rest) = line.strip_prefix("PERFORMER ") {
+                let performer = Self::unquote(rest);
+                match &mut current {
+                    Some(track) => track.performer = Some(performer),
+                    None => album_performer = Some(performer),
+                }
+            } else if let Some(rest) = line.strip_prefix("TRACK ") {
+                if let Some(track) = current.take() {
+                    entries.push(track);
+                }
+                let track_number = rest
+                    .split_whitespace()
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .unwrap_or((entries.len() + 1) as u32);
+
+                current = Some(CueTrackEntry {
+                    track_number,
+                    title: None,
+                    performer: None,
+                    start_frame: 0,
+                });
+            } else if let Some(rest) = line.strip_prefix("INDEX 01 ") {
+                if let Some(track) = &mut current {
+                    track.start_frame = Self::parse_timecode(rest.trim())?;
+                }
+            }
+        }
+
+        if let Some(track) = current.take() {
+            entries.push(track);
+        }
+
+        if entries.is_empty() {
+            return Err("CUE sheet contains no TRACK entries".to_string());
+        }
+
+        let mut results = Vec::new();
+        for (i, entry) in entries.iter().enumerate() {
+            let start_sample = entry.start_frame * audio_file_sample_rate as u64 / FRAMES_PER_SECOND as u64;
+
+            let end_sample = entries.get(i + 1).map(|next| {
+                next.start_frame * audio_file_sample_rate as u64 / FRAMES_PER_SECOND as u64
+            });
+
+            let duration = match end_sample {
+                Some(end) => (end - start_sample) as f64 / audio_file_sample_rate as f64,
+                None => total_duration_secs - (start_sample as f64 / audio_file_sample_rate as f64),
+            };
+
+            let mut metadata = AudioMetadata::default();
+            metadata.title = entry.title.clone().or_else(|| album.clone());
+            metadata.artist = entry.performer.clone().or_else(|| album_performer.clone());
+            metadata.album = album.clone();
+            metadata.track_number = Some(entry.track_number);
+            metadata.duration = Some(duration.max(0.0));
+            metadata.sample_rate = Some(audio_file_sample_rate);
+
+            results.push((metadata, start_sample, end_sample));
+        }
+
+        Ok(results)
+    }
+
+    fn unquote(value: &str) -> String {
+        value.trim().trim_matches('"').to_string()
+    }
+
+    /// Parse a Red Book `MM:SS:FF` timecode into an absolute frame count
+    fn parse_timecode(value: &str) -> Result<u64, String> {
+        let parts: Vec<&str> = value.split(':').collect();
+        if parts.len() != 3 {
+            return Err(format!("Invalid CUE timecode: {}", value));
+        }
+
+        let minutes: u64 = parts[0].parse().map_err(|_| format!("Invalid minutes in timecode: {}", value))?;
+        let seconds: u64 = parts[1].parse().map_err(|_| format!("Invalid seconds in timecode: {}", value))?;
+        let frames: u64 = parts[2].parse().map_err(|_| format!("Invalid frames in timecode: {}", value))?;
+
+        Ok((minutes * 60 + seconds) * FRAMES_PER_SECOND as u64 + frames)
+    }
+
+    /// Find a `.cue` sheet alongside an audio file, if one exists
+    pub fn find_companion(audio_path: &Path) -> Option<std::path::PathBuf> {
+        let cue_path = audio_path.with_extension("cue");
+        if cue_path.is_file() {
+            Some(cue_path)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for CueParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_timecode() {
+        assert_eq!(CueParser::parse_timecode("00:00:00").unwrap(), 0);
+        assert_eq!(CueParser::parse_timecode("03:25:37").unwrap(), (3 * 60 + 25) * 75 + 37);
+    }
+
+    #[test]
+    fn test_parse_timecode_invalid() {
+        assert!(CueParser::parse_timecode("not-a-timecode").is_err());
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::AudioFeatures;
+
+/// On-disk cache of extracted `AudioFeatures`, keyed by content hash, so
+/// rescanning a library only re-analyzes files that actually changed.
+pub struct FeatureCache {
+    path: PathBuf,
+    entries: HashMap<String, AudioFeatures>,
+}
+
+impl FeatureCache {
+    /// Load the cache from `path`, starting empty if it doesn't exist yet
+    pub fn load(path: PathBuf) -> Self {
+        let entries = fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self { path, entries }
+    }
+
+    pub fn get(&self, content_hash: &str) -> Option<&AudioFeatures> {
+        self.entries.get(content_hash)
+    }
+
+    pub fn insert(&mut self, features: AudioFeatures) {
+        self.entries.insert(features.content_hash.clone(), features);
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &AudioFeatures> {
+        self.entries.values()
+    }
+
+    /// Persist the cache back to disk
+    pub fn save(&self) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(|e| format!("Failed to create cache dir: {}", e))?;
+        }
+
+        let json = serde_json::to_string(&self.entries)
+            .map_err(|e| format!("Failed to serialize feature cache: {}", e))?;
+
+        fs::write(&self.path, json).map_err(|e| format!("Failed to write feature cache: {}", e))
+    }
+
+    /// Default cache location alongside the application's other local data
+    pub fn default_path(app_data_dir: &Path) -> PathBuf {
+        app_data_dir.join("feature_cache.json")
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::hash_map::DefaultHasher;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::Path;
+
+use rustfft::{num_complex::Complex32, FftPlanner};
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::models::AudioFeatures;
+use crate::services::SecurityValidator;
+
+const ANALYSIS_WINDOW: usize = 2048;
+const ANALYSIS_HOP: usize = 1024;
+
+/// Decodes audio and computes a fixed-length content descriptor used for
+/// similarity search and automatic playlist sequencing.
+pub struct FeatureExtractor;
+
+impl FeatureExtractor {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Extract a content-based feature vector from an audio file
+    pub fn extract(&self, file_path: &str) -> Result<AudioFeatures, String> {
+        let path = Path::new(file_path);
+        SecurityValidator::validate_audio_file(path)?;
+
+        let content_hash = Self::content_hash(path)?;
+        let samples = Self::decode_to_mono(path)?;
+        let sample_rate = Self::probe_sample_rate(path)?;
+
+        Ok(Self::analyze(samples, sample_rate, content_hash, file_path.to_string()))
+    }
+
+    /// Hash the file contents so rescans of an unchanged file are cheap.
+    /// `pub(crate)` so callers (e.g. `analyze_library_features`) can check
+    /// `FeatureCache` for this hash *before* paying for `extract`'s decode.
+    pub(crate) fn content_hash(path: &Path) -> Result<String, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        Ok(format!("{:016x}", hasher.finish()))
+    }
+
+    fn probe_sample_rate(path: &Path) -> Result<u32, String> {
+        let file = fs::File::open(path).map_err(|e| e.to_string())?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let probed = symphonia::default::get_probe()
+            .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| format!("Failed to probe audio: {}", e))?;
+
+        let track = probed.format.default_track().ok_or("No decodable track found")?;
+        Ok(track.codec_params.sample_rate.unwrap_or(44100))
+    }
+
+    /// Decode the entire file to a single channel of normalized f32 samples
+    fn decode_to_mono(path: &Path) -> Result<Vec<f32>, String> {
+        let file = fs::File::open(path).map_err(|e| e.to_string())?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let mut probed = symphonia::default::get_probe()
+            .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| format!("Failed to probe audio: {}", e))?;
+
+        let track = probed.format.default_track().ok_or("No decodable track found")?.clone();
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| format!("Failed to create decoder: {}", e))?;
+
+        let mut mono = Vec::new();
+        loop {
+            let packet = match probed.format.next_packet() {
+                Ok(p) => p,
+                Err(_) => break,
+            };
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+
+            let spec = *decoded.spec();
+            let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buf.copy_interleaved_ref(decoded);
+
+            let channels = spec.channels.count().max(1);
+            for frame in buf.samples().chunks(channels) {
+                let sum: f32 = frame.iter().sum();
+                mono.push(sum / channels as f32);
+            }
+        }
+
+        Ok(mono)
+    }
+
+    /// Compute the descriptor vector from decoded mono samples
+    fn analyze(samples: Vec<f32>, sample_rate: u32, content_hash: String, source_path: String) -> AudioFeatures {
+        if samples.is_empty() {
+            return AudioFeatures {
+                content_hash,
+                source_path,
+                tempo_bpm: 0.0,
+                spectral_centroid: 0.0,
+                spectral_rolloff: 0.0,
+                zero_crossing_rate: 0.0,
+                rms_energy: 0.0,
+                chroma: [0.0; 12],
+            };
+        }
+
+        let zero_crossing_rate = Self::zero_crossing_rate(&samples);
+        let rms_energy = Self::rms_energy(&samples);
+
+        let mut fft_planner = FftPlanner::<f32>::new();
+        let fft = fft_planner.plan_fft_forward(ANALYSIS_WINDOW);
+
+        let mut centroid_sum = 0.0f32;
+        let mut rolloff_sum = 0.0f32;
+        let mut chroma = [0.0f32; 12];
+        let mut onset_envelope = Vec::new();
+        let mut num_windows = 0usize;
+        let mut prev_magnitude: Option<Vec<f32>> = None;
+
+        let mut pos = 0;
+        while pos + ANALYSIS_WINDOW <= samples.len() {
+            let window = &samples[pos..pos + ANALYSIS_WINDOW];
+            let mut spectrum: Vec<Complex32> = window
+                .iter()
+                .enumerate()
+                .map(|(i, &s)| {
+                    // Hann window to reduce spectral leakage
+                    let hann = 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32
+                        / (ANALYSIS_WINDOW - 1) as f32).cos();
+                    Complex32::new(s * hann, 0.0)
+                })
+                .collect();
+            fft.process(&mut spectrum);
+
+            let bins = ANALYSIS_WINDOW / 2;
+            let magnitude: Vec<f32> = spectrum[..bins].iter().map(|c| c.norm()).collect();
+
+            let (c, r) = Self::centroid_and_rolloff(&magnitude, sample_rate);
+            centroid_sum += c;
+            rolloff_sum += r;
+
+            Self::accumulate_chroma(&magnitude, sample_rate, &mut chroma);
+
+            // Onset strength: positive spectral-flux between consecutive frames
+            if let Some(prev) = &prev_magnitude {
+                let flux: f32 = magnitude.iter().zip(prev.iter())
+                    .map(|(m, p)| (m - p).max(0.0))
+                    .sum();
+                onset_envelope.push(flux);
+            }
+            prev_magnitude = Some(magnitude);
+
+            num_windows += 1;
+            pos += ANALYSIS_HOP;
+        }
+
+        let num_windows = num_windows.max(1) as f32;
+        let chroma_sum: f32 = chroma.iter().sum::<f32>().max(1e-9);
+        for bin in &mut chroma {
+            *bin /= chroma_sum;
+        }
+
+        let tempo_bpm = Self::estimate_tempo(&onset_envelope, sample_rate);
+
+        AudioFeatures {
+            content_hash,
+            source_path,
+            tempo_bpm,
+            spectral_centroid: centroid_sum / num_windows,
+            spectral_rolloff: rolloff_sum / num_windows,
+            zero_crossing_rate,
+            rms_energy,
+            chroma,
+        }
+    }
+
+    fn zero_crossing_rate(samples: &[f32]) -> f32 {
+        let crossings = samples.windows(2)
+            .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+            .count();
+        crossings as f32 / samples.len().max(1) as f32
+    }
+
+    fn rms_energy(samples: &[f32]) -> f32 {
+        let sum: f32 = samples.iter().map(|s| s * s).sum();
+        (sum / samples.len().max(1) as f32).sqrt()
+    }
+
+    /// Spectral centroid (Hz) and 85%-energy rolloff (Hz) of one magnitude spectrum
+    fn centroid_and_rolloff(magnitude: &[f32], sample_rate: u32) -> (f32, f32) {
+        let total_energy: f32 = magnitude.iter().sum();
+        if total_energy <= 0.0 {
+            return (0.0, 0.0);
+        }
+
+        let bin_hz = sample_rate as f32 / (2 * magnitude.len()) as f32;
+
+        let weighted: f32 = magnitude.iter().enumerate()
+            .map(|(i, &m)| i as f32 * bin_hz * m)
+            .sum();
+        let centroid = weighted / total_energy;
+
+        let rolloff_threshold = 0.85 * total_energy;
+        let mut cumulative = 0.0;
+        let mut rolloff_bin = magnitude.len() - 1;
+        for (i, &m) in magnitude.iter().enumerate() {
+            cumulative += m;
+            if cumulative >= rolloff_threshold {
+                rolloff_bin = i;
+                break;
+            }
+        }
+        let rolloff = rolloff_bin as f32 * bin_hz;
+
+        (centroid, rolloff)
+    }
+
+    /// Fold FFT bins into 12 pitch classes (A440 equal temperament)
+    fn accumulate_chroma(magnitude: &[f32], sample_rate: u32, chroma: &mut [f32; 12]) {
+        let bin_hz = sample_rate as f32 / (2 * magnitude.len()) as f32;
+
+        for (i, &m) in magnitude.iter().enumerate() {
+            let freq = i as f32 * bin_hz;
+            if freq < 20.0 {
+                continue;
+            }
+            let midi = 69.0 + 12.0 * (freq / 440.0).log2();
+            let pitch_class = midi.round().rem_euclid(12.0) as usize;
+            chroma[pitch_class.min(11)] += m;
+        }
+    }
+
+    /// Estimate tempo from the onset-strength envelope via autocorrelation
+    /// of inter-onset intervals, searching the 60-180 BPM range
+    fn estimate_tempo(onset_envelope: &[f32], sample_rate: u32) -> f32 {
+        if onset_envelope.len() < 4 {
+            return 0.0;
+        }
+
+        let frame_rate = sample_rate as f32 / ANALYSIS_HOP as f32;
+        let min_lag = (frame_rate * 60.0 / 180.0).round() as usize; // 180 BPM
+        let max_lag = (frame_rate * 60.0 / 60.0).round() as usize; // 60 BPM
+
+        let mean = onset_envelope.iter().sum::<f32>() / onset_envelope.len() as f32;
+        let centered: Vec<f32> = onset_envelope.iter().map(|v| v - mean).collect();
+
+        let mut best_lag = min_lag.max(1);
+        let mut best_score = f32::MIN;
+
+        for lag in min_lag.max(1)..=max_lag.min(centered.len().saturating_sub(1)) {
+            let score: f32 = centered.iter().zip(centered.iter().skip(lag))
+                .map(|(a, b)| a * b)
+                .sum();
+            if score > best_score {
+                best_score = score;
+                best_lag = lag;
+            }
+        }
+
+        if best_lag == 0 {
+            0.0
+        } else {
+            60.0 * frame_rate / best_lag as f32
+        }
+    }
+}
+
+impl Default for FeatureExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,8 +1,47 @@
+use std::collections::VecDeque;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
 use tauri::{Emitter, WebviewWindow};
-use crate::models::ScanProgress;
-use crate::services::SecurityValidator;
+use lofty::file::AudioFile;
+use crate::models::{ScanProgress, VirtualTrackPath};
+use crate::services::{CueParser, SecurityValidator};
+
+/// Number of directory-walker worker threads. A bounded set rather than one
+/// thread per directory keeps a library with deeply nested folders from
+/// spawning unbounded threads.
+const WORKER_COUNT: usize = 4;
+
+/// Emit `scan-progress` only every N discovered files, so a multi-thousand
+/// file library doesn't flood the event channel.
+const PROGRESS_EMIT_EVERY: usize = 10;
+
+/// How long an idle worker waits before re-checking the queue for work
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// Shared cancellation flag for an in-progress scan. `cancel_scan` flips it
+/// (via the `ScanState` Tauri-managed state holding one of these); workers
+/// poll it and drain out, returning whatever was already found.
+#[derive(Default)]
+pub struct ScanCancellation(AtomicBool);
+
+impl ScanCancellation {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    pub fn reset(&self) {
+        self.0.store(false, Ordering::Relaxed);
+    }
+}
 
 /// File scanner service - responsible for recursively scanning directories for audio files
 pub struct FileScanner {
@@ -21,101 +60,236 @@ impl FileScanner {
         }
     }
 
-    /// Scan directory recursively for audio files with security validation
+    /// Scan directory recursively for audio files with security validation.
+    /// Walks with a bounded pool of worker threads sharing one directory
+    /// queue, an atomic file counter (for both progress throttling and the
+    /// `max_files_per_scan` guard), and `cancellation` so a `cancel_scan`
+    /// call can stop an in-progress scan from another command invocation.
     pub fn scan_directory(
         &self,
         path: &Path,
         window: &WebviewWindow,
+        cancellation: Arc<ScanCancellation>,
     ) -> Result<Vec<String>, String> {
-        // Validate directory before scanning
         SecurityValidator::validate_directory(path)?;
+        cancellation.reset();
 
-        let mut audio_files = Vec::new();
-        self.scan_recursive(path, &mut audio_files, window)?;
-        
-        // Emit completion event
-        let _ = window.emit("scan-complete", ScanProgress::completed(audio_files.len()));
-        
-        Ok(audio_files)
-    }
+        let queue: Arc<Mutex<VecDeque<PathBuf>>> =
+            Arc::new(Mutex::new(VecDeque::from([path.to_path_buf()])));
+        let in_flight = Arc::new(AtomicUsize::new(1));
+        let file_count = Arc::new(AtomicUsize::new(0));
+        let limit_reached = Arc::new(AtomicBool::new(false));
+        let results: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
 
-    fn scan_recursive(
-        &self,
-        dir: &Path,
-        files: &mut Vec<String>,
-        window: &WebviewWindow,
-    ) -> Result<(), String> {
-        // Check scan limit
-        if files.len() >= self.max_files_per_scan {
+        let worker_count = WORKER_COUNT
+            .min(thread::available_parallelism().map(|n| n.get()).unwrap_or(WORKER_COUNT));
+
+        let handles: Vec<_> = (0..worker_count)
+            .map(|_| {
+                let queue = Arc::clone(&queue);
+                let in_flight = Arc::clone(&in_flight);
+                let file_count = Arc::clone(&file_count);
+                let limit_reached = Arc::clone(&limit_reached);
+                let cancellation = Arc::clone(&cancellation);
+                let results = Arc::clone(&results);
+                let window = window.clone();
+                let audio_extensions = self.audio_extensions.clone();
+                let max_files = self.max_files_per_scan;
+
+                thread::spawn(move || {
+                    Self::worker_loop(
+                        queue,
+                        in_flight,
+                        file_count,
+                        limit_reached,
+                        cancellation,
+                        results,
+                        window,
+                        audio_extensions,
+                        max_files,
+                    )
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let _ = handle.join();
+        }
+
+        let results = Arc::try_unwrap(results)
+            .map(|m| m.into_inner().unwrap_or_default())
+            .unwrap_or_default();
+
+        if limit_reached.load(Ordering::Relaxed) {
             return Err(format!(
                 "Maximum file limit reached ({} files). Please scan smaller directories.",
                 self.max_files_per_scan
             ));
         }
 
-        if !dir.is_dir() {
-            return Ok(());
+        if cancellation.is_cancelled() {
+            let _ = window.emit("scan-cancelled", ScanProgress::new(results.len(), results.len(), String::new()));
+        } else {
+            let _ = window.emit("scan-complete", ScanProgress::completed(results.len()));
         }
 
-        let entries = fs::read_dir(dir).map_err(|e| {
-            eprintln!("Failed to read directory {:?}: {}", dir, e);
-            format!("Failed to read directory: {}", e)
-        })?;
+        Ok(results)
+    }
+
+    /// One worker's share of the walk: pop a directory, list it, push any
+    /// subdirectories back onto the shared queue, and record audio files.
+    /// Exits once the queue is empty and no directory is still in flight
+    /// anywhere (i.e. there's truly no more work, not just none queued yet).
+    #[allow(clippy::too_many_arguments)]
+    fn worker_loop(
+        queue: Arc<Mutex<VecDeque<PathBuf>>>,
+        in_flight: Arc<AtomicUsize>,
+        file_count: Arc<AtomicUsize>,
+        limit_reached: Arc<AtomicBool>,
+        cancellation: Arc<ScanCancellation>,
+        results: Arc<Mutex<Vec<String>>>,
+        window: WebviewWindow,
+        audio_extensions: Vec<&'static str>,
+        max_files: usize,
+    ) {
+        let cue_parser = CueParser::new();
+
+        loop {
+            let dir = queue.lock().unwrap().pop_front();
+
+            let Some(dir) = dir else {
+                if in_flight.load(Ordering::Acquire) == 0 {
+                    return;
+                }
+                thread::sleep(QUEUE_POLL_INTERVAL);
+                continue;
+            };
+
+            if cancellation.is_cancelled() || limit_reached.load(Ordering::Relaxed) {
+                in_flight.fetch_sub(1, Ordering::AcqRel);
+                continue;
+            }
 
-        for entry in entries {
-            let entry = match entry {
+            let entries = match fs::read_dir(&dir) {
                 Ok(e) => e,
                 Err(e) => {
-                    eprintln!("Failed to read entry: {}", e);
-                    continue; // Skip problematic entries
+                    eprintln!("Failed to read directory {:?}: {}", dir, e);
+                    in_flight.fetch_sub(1, Ordering::AcqRel);
+                    continue;
                 }
             };
 
-            let path = entry.path();
+            let mut subdirs = Vec::new();
 
-            if path.is_dir() {
-                // Recursively scan subdirectories with error handling
-                if let Err(e) = self.scan_recursive(&path, files, window) {
-                    eprintln!("Error scanning subdirectory {:?}: {}", path, e);
-                    // Continue scanning other directories
-                    continue;
+            for entry in entries {
+                if cancellation.is_cancelled() {
+                    break;
                 }
-            } else if path.is_file() {
-                if self.is_audio_file(&path) {
-                    // Validate file before adding
-                    match SecurityValidator::validate_audio_file(&path) {
+                if file_count.load(Ordering::Relaxed) >= max_files {
+                    limit_reached.store(true, Ordering::Relaxed);
+                    break;
+                }
+
+                let entry = match entry {
+                    Ok(e) => e,
+                    Err(e) => {
+                        eprintln!("Failed to read entry: {}", e);
+                        continue;
+                    }
+                };
+                let entry_path = entry.path();
+
+                if entry_path.is_dir() {
+                    subdirs.push(entry_path);
+                } else if entry_path.is_file() && Self::is_audio_file(&audio_extensions, &entry_path) {
+                    match SecurityValidator::validate_audio_file(&entry_path) {
                         Ok(_) => {
-                            if let Some(path_str) = path.to_str() {
-                                files.push(path_str.to_string());
-
-                                // Emit progress every 10 files
-                                if files.len() % 10 == 0 {
-                                    let _ = window.emit(
-                                        "scan-progress",
-                                        ScanProgress::new(files.len(), 0, path_str.to_string()),
-                                    );
-                                }
+                            Self::record_audio_file(&cue_parser, &entry_path, &results);
+
+                            let count = file_count.fetch_add(1, Ordering::Relaxed) + 1;
+                            if count % PROGRESS_EMIT_EVERY == 0 {
+                                let _ = window.emit(
+                                    "scan-progress",
+                                    ScanProgress::new(count, 0, entry_path.to_string_lossy().to_string()),
+                                );
                             }
                         }
                         Err(e) => {
-                            eprintln!("Skipping invalid file {:?}: {}", path, e);
-                            // Skip invalid files but continue scanning
+                            eprintln!("Skipping invalid file {:?}: {}", entry_path, e);
                         }
                     }
                 }
             }
+
+            if !subdirs.is_empty() {
+                in_flight.fetch_add(subdirs.len(), Ordering::AcqRel);
+                queue.lock().unwrap().extend(subdirs);
+            }
+            in_flight.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+
+    /// If `path` has a companion `.cue` sheet, push one virtual track entry
+    /// per `TRACK` instead of the raw file path; otherwise push the file path.
+    fn record_audio_file(cue_parser: &CueParser, path: &Path, results: &Mutex<Vec<String>>) {
+        if let Some(pushed) = Self::expand_cue_companion(cue_parser, path) {
+            results.lock().unwrap().extend(pushed);
+            return;
         }
 
-        Ok(())
+        if let Some(path_str) = path.to_str() {
+            results.lock().unwrap().push(path_str.to_string());
+        }
     }
 
-    fn is_audio_file(&self, path: &Path) -> bool {
-        if let Some(ext) = path.extension() {
-            if let Some(ext_str) = ext.to_str() {
-                return self.audio_extensions.contains(&ext_str.to_lowercase().as_str());
+    /// Returns `Some(virtual_track_paths)` if `path` has a companion CUE
+    /// sheet, `None` if it should be pushed as a plain file path instead.
+    fn expand_cue_companion(cue_parser: &CueParser, path: &Path) -> Option<Vec<String>> {
+        let cue_path = CueParser::find_companion(path)?;
+
+        let properties = match lofty::read_from_path(path) {
+            Ok(tagged_file) => tagged_file.properties().clone(),
+            Err(e) => {
+                eprintln!("Failed to read properties for CUE-backed file {:?}: {}", path, e);
+                return None;
             }
-        }
-        false
+        };
+
+        let sample_rate = properties.sample_rate().unwrap_or(44100);
+        let total_duration = properties.duration().as_secs_f64();
+
+        let tracks = match cue_parser.parse(&cue_path, sample_rate, total_duration) {
+            Ok(t) => t,
+            Err(e) => {
+                eprintln!("Failed to parse CUE sheet {:?}: {}", cue_path, e);
+                return None;
+            }
+        };
+
+        let parent_file = path.to_string_lossy().to_string();
+        Some(
+            tracks
+                .into_iter()
+                .map(|(_, start_sample, end_sample)| {
+                    VirtualTrackPath::new(parent_file.clone(), start_sample, end_sample).encode()
+                })
+                .collect(),
+        )
+    }
+
+    /// Whether `path` should be treated as an audio file: a whitelisted
+    /// extension is the fast path, but a file with no extension (or one we
+    /// don't recognize) still gets a chance via content sniffing, so a
+    /// legitimately-renamed or extensionless audio file isn't silently
+    /// skipped during the directory walk.
+    fn is_audio_file(extensions: &[&str], path: &Path) -> bool {
+        Self::matches_audio_extension(extensions, path) || SecurityValidator::detect_format(path).is_ok()
+    }
+
+    fn matches_audio_extension(extensions: &[&str], path: &Path) -> bool {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|ext| extensions.contains(&ext.to_lowercase().as_str()))
     }
 }
 
@@ -124,3 +298,23 @@ impl Default for FileScanner {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn extensionless_audio_file_is_admitted_via_content_sniffing() {
+        let path = std::env::temp_dir().join("sonantica_test_scanner_no_extension");
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(b"fLaC\x00\x00\x00\x00").unwrap();
+        drop(file);
+
+        let extensions = FileScanner::new().audio_extensions;
+        assert!(FileScanner::is_audio_file(&extensions, &path));
+        assert!(SecurityValidator::validate_audio_file(&path).is_ok());
+
+        let _ = std::fs::remove_file(path);
+    }
+}
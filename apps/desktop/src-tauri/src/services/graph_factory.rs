@@ -0,0 +1,112 @@
+use compositor::nodes::{CompressorNode, DenoiseNode, EqualizerNode, GainNode, LoudnessNormNode};
+use espectro::{AudioNode, GraphError, NodeFactory, Result};
+use orquestador::nodes::{ChannelStripNode, LoudnessMeterNode, MixerNode, PanNode, ResamplerNode};
+use std::collections::HashMap;
+
+/// Default band count for a freshly-created `EqualizerNode` with no prior
+/// `band_N_*` parameters to recover a count from.
+const DEFAULT_EQ_BANDS: usize = 3;
+/// Default input count for a freshly-created `MixerNode` with no prior
+/// `input_N_*` parameters to recover a count from.
+const DEFAULT_MIXER_INPUTS: usize = 2;
+
+/// Recover a structural count (e.g. EQ band count, mixer input count) from a
+/// serialized parameter map by finding the highest `<prefix>_<N>_*` index
+/// present and adding one. `EqualizerNode`/`MixerNode` size their band/input
+/// vectors at construction time, so this must run *before* constructing the
+/// node - applying `set_parameter("band_3_gain", ...)` to an already-built
+/// 3-band node just returns `ParameterNotFound`.
+fn structural_count(parameters: &HashMap<String, f32>, prefix: &str, default: usize) -> usize {
+    parameters
+        .keys()
+        .filter_map(|key| key.strip_prefix(prefix))
+        .filter_map(|rest| rest.split('_').next())
+        .filter_map(|index| index.parse::<usize>().ok())
+        .max()
+        .map_or(default, |highest_index| highest_index + 1)
+}
+
+/// Concrete `NodeFactory` matching a `node_type` tag to a real constructor
+/// from one of the plugin crates.
+///
+/// This is the one place in the app that needs to know about both
+/// Compositor and Orquestador node types at once; the plugins themselves
+/// never reference each other.
+pub struct GraphFactory;
+
+impl NodeFactory for GraphFactory {
+    fn create(
+        &self,
+        node_type: &str,
+        id: String,
+        parameters: &HashMap<String, f32>,
+    ) -> Result<Box<dyn AudioNode>> {
+        match node_type {
+            "GainNode" => Ok(Box::new(GainNode::new(id))),
+            "CompressorNode" => Ok(Box::new(CompressorNode::new(id))),
+            "EqualizerNode" => {
+                let num_bands = structural_count(parameters, "band_", DEFAULT_EQ_BANDS);
+                Ok(Box::new(EqualizerNode::new(id, num_bands)))
+            }
+            "ChannelStripNode" => Ok(Box::new(ChannelStripNode::new(id))),
+            "PanNode" => Ok(Box::new(PanNode::new(id))),
+            "MixerNode" => {
+                let num_inputs = structural_count(parameters, "input_", DEFAULT_MIXER_INPUTS);
+                Ok(Box::new(MixerNode::new(id, num_inputs)))
+            }
+            "LoudnessMeterNode" => Ok(Box::new(LoudnessMeterNode::new(id))),
+            "LoudnessNormNode" => Ok(Box::new(LoudnessNormNode::new(id))),
+            "DenoiseNode" => Ok(Box::new(DenoiseNode::new(id))),
+            "ResamplerNode" => Ok(Box::new(ResamplerNode::new(id, 48000))),
+            other => Err(GraphError::UnknownNodeType(other.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_structural_count_defaults_when_no_matching_keys() {
+        let parameters = HashMap::new();
+        assert_eq!(structural_count(&parameters, "band_", DEFAULT_EQ_BANDS), DEFAULT_EQ_BANDS);
+    }
+
+    #[test]
+    fn test_structural_count_recovers_highest_index_plus_one() {
+        let mut parameters = HashMap::new();
+        parameters.insert("band_0_gain".to_string(), 0.0);
+        parameters.insert("band_4_gain".to_string(), 1.0);
+        parameters.insert("band_4_freq".to_string(), 500.0);
+
+        assert_eq!(structural_count(&parameters, "band_", DEFAULT_EQ_BANDS), 5);
+    }
+
+    #[test]
+    fn test_graph_factory_rebuilds_eq_with_recovered_band_count() {
+        let mut parameters = HashMap::new();
+        for i in 0..5 {
+            parameters.insert(format!("band_{}_gain", i), 2.0);
+        }
+
+        let node = GraphFactory.create("EqualizerNode", "eq1".to_string(), &parameters).unwrap();
+
+        // All 5 bands' parameters must exist on the rebuilt node, not just
+        // the first `DEFAULT_EQ_BANDS` - the factory only decides band
+        // count, `GraphDocument::apply_to_graph` applies the actual values.
+        assert!(node.get_parameter("band_4_gain").is_some());
+        assert_eq!(node.metadata().parameters.len(), 5 * 4);
+    }
+
+    #[test]
+    fn test_graph_factory_rebuilds_mixer_with_recovered_input_count() {
+        let mut parameters = HashMap::new();
+        for i in 0..4 {
+            parameters.insert(format!("input_{}_gain", i), 0.0);
+        }
+
+        let node = GraphFactory.create("MixerNode", "mixer1".to_string(), &parameters).unwrap();
+        assert_eq!(node.get_parameter("input_3_gain"), Some(0.0));
+    }
+}
@@ -0,0 +1,372 @@
+use std::fs;
+use std::path::Path;
+
+use lofty::file::TaggedFileExt;
+use lofty::tag::ItemKey;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::models::{AlbumLoudness, LoudnessInfo};
+use crate::services::SecurityValidator;
+
+/// ReplayGain 2.0's reference level. EBU R128 broadcast delivery normally
+/// targets -23 LUFS instead; pass a different target to `with_target` for that.
+const DEFAULT_TARGET_LUFS: f32 = -18.0;
+
+/// BS.1770 measurement window and hop, as a fraction of a second
+const BLOCK_SECONDS: f64 = 0.4;
+const HOP_SECONDS: f64 = 0.1;
+
+/// Blocks quieter than this are silence/near-silence and never count towards
+/// the integrated measurement, even before the relative gate is computed
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+
+/// Blocks more than this many LU below the mean of the absolute-gated blocks
+/// are dropped in the second gating pass
+const RELATIVE_GATE_OFFSET_LU: f64 = 10.0;
+
+/// Per-channel weight applied to mean-square energy before summing, per
+/// ITU-R BS.1770. We don't have full channel-layout metadata from the
+/// decoder, so channels 0 and 1 (L/R, or the only channel in mono) are
+/// treated as front channels and any further channel is treated as a
+/// surround channel.
+const FRONT_CHANNEL_WEIGHT: f64 = 1.0;
+const SURROUND_CHANNEL_WEIGHT: f64 = 1.41;
+
+/// Computes ITU-R BS.1770 / EBU R128 integrated loudness and a suggested
+/// ReplayGain-style track (or album) gain for audio files, at scan time.
+pub struct LoudnessAnalyzer {
+    target_lufs: f32,
+}
+
+impl LoudnessAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            target_lufs: DEFAULT_TARGET_LUFS,
+        }
+    }
+
+    pub fn with_target(target_lufs: f32) -> Self {
+        Self { target_lufs }
+    }
+
+    /// Measure a single file's integrated loudness, peak, and suggested gain
+    pub fn analyze(&self, file_path: &str) -> Result<LoudnessInfo, String> {
+        let path = Path::new(file_path);
+        SecurityValidator::validate_audio_file(path)?;
+
+        let (block_energies, sample_peak) = Self::measure_blocks(path)?;
+        let integrated_lufs = integrated_loudness(&block_energies);
+
+        Ok(LoudnessInfo {
+            file_path: file_path.to_string(),
+            integrated_lufs,
+            sample_peak,
+            track_gain_db: self.track_gain(integrated_lufs),
+        })
+    }
+
+    /// Measure every file in an album together: each track still gets its own
+    /// integrated loudness and peak, but the gain applied to all of them
+    /// comes from one gated pass pooling every file's blocks, so the album
+    /// plays back at a consistent level instead of each track being
+    /// normalized to the same loudness independently.
+    pub fn analyze_album(&self, file_paths: &[String]) -> Result<AlbumLoudness, String> {
+        let mut tracks = Vec::with_capacity(file_paths.len());
+        let mut pooled_blocks = Vec::new();
+
+        for file_path in file_paths {
+            let path = Path::new(file_path);
+            SecurityValidator::validate_audio_file(path)?;
+
+            let (block_energies, sample_peak) = Self::measure_blocks(path)?;
+            let integrated_lufs = integrated_loudness(&block_energies);
+
+            tracks.push(LoudnessInfo {
+                file_path: file_path.clone(),
+                integrated_lufs,
+                sample_peak,
+                track_gain_db: self.track_gain(integrated_lufs),
+            });
+            pooled_blocks.extend(block_energies);
+        }
+
+        let album_integrated_lufs = integrated_loudness(&pooled_blocks);
+
+        Ok(AlbumLoudness {
+            tracks,
+            album_integrated_lufs,
+            album_gain_db: self.track_gain(album_integrated_lufs),
+        })
+    }
+
+    /// Write `ReplayGainTrackGain`/`ReplayGainTrackPeak` tags to the file,
+    /// in the conventional `"<gain> dB"` / `"<peak>"` text forms other
+    /// players expect.
+    pub fn write_replaygain_tags(file_path: &str, info: &LoudnessInfo) -> Result<(), String> {
+        let path = Path::new(file_path);
+        SecurityValidator::validate_audio_file(path)?;
+
+        let mut tagged_file =
+            lofty::read_from_path(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+        if tagged_file.primary_tag().is_none() {
+            let tag_type = tagged_file.primary_tag_type();
+            tagged_file.insert_tag(lofty::tag::Tag::new(tag_type));
+        }
+
+        let tag = tagged_file
+            .primary_tag_mut()
+            .ok_or_else(|| "File has no writable tag".to_string())?;
+
+        tag.insert_text(
+            ItemKey::ReplayGainTrackGain,
+            format!("{:.2} dB", info.track_gain_db),
+        );
+        tag.insert_text(
+            ItemKey::ReplayGainTrackPeak,
+            format!("{:.6}", info.sample_peak),
+        );
+
+        tagged_file
+            .save_to_path(path, lofty::config::WriteOptions::default())
+            .map_err(|e| format!("Failed to write ReplayGain tags: {}", e))
+    }
+
+    fn track_gain(&self, integrated_lufs: f64) -> f32 {
+        if integrated_lufs.is_finite() {
+            self.target_lufs - integrated_lufs as f32
+        } else {
+            0.0
+        }
+    }
+
+    /// Decode the file, K-weight every channel, and return the per-block
+    /// weighted mean-square energy (one entry per 400ms block, 100ms hop)
+    /// alongside the highest absolute sample value seen
+    fn measure_blocks(path: &Path) -> Result<(Vec<f64>, f32), String> {
+        let file = fs::File::open(path).map_err(|e| e.to_string())?;
+        let mss = MediaSourceStream::new(Box::new(file), Default::default());
+        let mut hint = Hint::new();
+        if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+            hint.with_extension(ext);
+        }
+
+        let mut probed = symphonia::default::get_probe()
+            .format(&hint, mss, &FormatOptions::default(), &MetadataOptions::default())
+            .map_err(|e| format!("Failed to probe audio: {}", e))?;
+
+        let track = probed.format.default_track().ok_or("No decodable track found")?.clone();
+        let sample_rate = track.codec_params.sample_rate.unwrap_or(44100);
+        let mut decoder = symphonia::default::get_codecs()
+            .make(&track.codec_params, &DecoderOptions::default())
+            .map_err(|e| format!("Failed to create decoder: {}", e))?;
+
+        let mut channels: Vec<Vec<f32>> = Vec::new();
+        let mut sample_peak = 0.0f32;
+
+        loop {
+            let packet = match probed.format.next_packet() {
+                Ok(p) => p,
+                Err(_) => break,
+            };
+
+            let decoded = match decoder.decode(&packet) {
+                Ok(d) => d,
+                Err(_) => continue,
+            };
+
+            let spec = *decoded.spec();
+            let channel_count = spec.channels.count().max(1);
+            if channels.is_empty() {
+                channels = vec![Vec::new(); channel_count];
+            }
+
+            let mut buf = SampleBuffer::<f32>::new(decoded.capacity() as u64, spec);
+            buf.copy_interleaved_ref(decoded);
+
+            for frame in buf.samples().chunks(channel_count) {
+                for (ch, &sample) in frame.iter().enumerate() {
+                    sample_peak = sample_peak.max(sample.abs());
+                    channels[ch].push(sample);
+                }
+            }
+        }
+
+        if channels.is_empty() || channels[0].is_empty() {
+            return Ok((Vec::new(), sample_peak));
+        }
+
+        let mut filters: Vec<KWeightingFilter> = channels.iter().map(|_| KWeightingFilter::new(sample_rate)).collect();
+        let weighted: Vec<Vec<f64>> = channels
+            .iter()
+            .zip(filters.iter_mut())
+            .map(|(samples, filter)| samples.iter().map(|&s| filter.process(s)).collect())
+            .collect();
+
+        let block_len = (sample_rate as f64 * BLOCK_SECONDS).round() as usize;
+        let hop_len = (sample_rate as f64 * HOP_SECONDS).round() as usize;
+        let frame_count = weighted[0].len();
+
+        let mut block_energies = Vec::new();
+        let mut pos = 0;
+        while pos + block_len <= frame_count {
+            let mut weighted_sum = 0.0f64;
+            for (ch, samples) in weighted.iter().enumerate() {
+                let weight = if ch < 2 { FRONT_CHANNEL_WEIGHT } else { SURROUND_CHANNEL_WEIGHT };
+                let mean_square: f64 = samples[pos..pos + block_len]
+                    .iter()
+                    .map(|&s| s * s)
+                    .sum::<f64>()
+                    / block_len as f64;
+                weighted_sum += weight * mean_square;
+            }
+            block_energies.push(weighted_sum);
+            pos += hop_len.max(1);
+        }
+
+        Ok((block_energies, sample_peak))
+    }
+}
+
+impl Default for LoudnessAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Two-pass gated mean of per-block weighted energies, per BS.1770: discard
+/// blocks below the absolute gate, average the survivors, then discard
+/// blocks more than 10 LU below that average and average again. Returns
+/// `f64::NEG_INFINITY` if every block is silent (nothing survives the
+/// absolute gate).
+fn integrated_loudness(block_energies: &[f64]) -> f64 {
+    let absolute_gate_z = lufs_to_energy(ABSOLUTE_GATE_LUFS);
+    let above_absolute: Vec<f64> = block_energies
+        .iter()
+        .copied()
+        .filter(|&z| z > absolute_gate_z)
+        .collect();
+
+    if above_absolute.is_empty() {
+        return f64::NEG_INFINITY;
+    }
+
+    let mean_z = mean(&above_absolute);
+    let relative_gate_lufs = energy_to_lufs(mean_z) - RELATIVE_GATE_OFFSET_LU;
+    let relative_gate_z = lufs_to_energy(relative_gate_lufs);
+
+    let above_relative: Vec<f64> = above_absolute
+        .iter()
+        .copied()
+        .filter(|&z| z > relative_gate_z)
+        .collect();
+
+    if above_relative.is_empty() {
+        return energy_to_lufs(mean_z);
+    }
+
+    energy_to_lufs(mean(&above_relative))
+}
+
+fn mean(values: &[f64]) -> f64 {
+    values.iter().sum::<f64>() / values.len() as f64
+}
+
+fn energy_to_lufs(z: f64) -> f64 {
+    -0.691 + 10.0 * z.log10()
+}
+
+fn lufs_to_energy(lufs: f64) -> f64 {
+    10f64.powf((lufs + 0.691) / 10.0)
+}
+
+/// ITU-R BS.1770 K-weighting: a ~+4 dB high-shelf around 1.5 kHz (the "head"
+/// pre-filter, approximating the acoustic effect of a human head) followed
+/// by a ~38 Hz high-pass (the "RLB" filter, modeling the ear's reduced
+/// sensitivity to very low frequencies). Coefficients are derived per
+/// sample rate via the bilinear transform of the standard's analog
+/// prototypes, so non-48kHz sources are still weighted correctly.
+struct KWeightingFilter {
+    stage1: Biquad,
+    stage2: Biquad,
+}
+
+impl KWeightingFilter {
+    fn new(sample_rate: u32) -> Self {
+        Self {
+            stage1: Biquad::head_shelf(sample_rate),
+            stage2: Biquad::high_pass_rlb(sample_rate),
+        }
+    }
+
+    fn process(&mut self, sample: f32) -> f64 {
+        let stage1_out = self.stage1.process(sample as f64);
+        self.stage2.process(stage1_out)
+    }
+}
+
+/// Direct Form II transposed biquad, with `a0` already normalized to 1
+struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    /// BS.1770 Annex 1 pre-filter: +4 dB shelf above ~1.68 kHz, Q ~0.707
+    fn head_shelf(sample_rate: u32) -> Self {
+        const F0: f64 = 1681.974450955533;
+        const GAIN_DB: f64 = 3.999843853973347;
+        const Q: f64 = 0.7071752369554196;
+
+        let k = (std::f64::consts::PI * F0 / sample_rate as f64).tan();
+        let vh = 10f64.powf(GAIN_DB / 20.0);
+        let vb = vh.powf(0.4996667741951464);
+
+        let a0 = 1.0 + k / Q + k * k;
+        Self {
+            b0: (vh + vb * k / Q + k * k) / a0,
+            b1: 2.0 * (k * k - vh) / a0,
+            b2: (vh - vb * k / Q + k * k) / a0,
+            a1: 2.0 * (k * k - 1.0) / a0,
+            a2: (1.0 - k / Q + k * k) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// BS.1770 Annex 1 RLB weighting: high-pass around ~38 Hz, Q ~0.5
+    fn high_pass_rlb(sample_rate: u32) -> Self {
+        const F0: f64 = 38.13547087602444;
+        const Q: f64 = 0.5003270373238773;
+
+        let k = (std::f64::consts::PI * F0 / sample_rate as f64).tan();
+        let a0 = 1.0 + k / Q + k * k;
+
+        Self {
+            b0: 1.0,
+            b1: -2.0,
+            b2: 1.0,
+            a1: 2.0 * (k * k - 1.0) / a0,
+            a2: (1.0 - k / Q + k * k) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    fn process(&mut self, x: f64) -> f64 {
+        let y = self.b0 * x + self.z1;
+        self.z1 = self.b1 * x - self.a1 * y + self.z2;
+        self.z2 = self.b2 * x - self.a2 * y;
+        y
+    }
+}
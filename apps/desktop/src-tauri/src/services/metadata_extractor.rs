@@ -4,8 +4,8 @@ use lofty::properties::FileProperties;
 use lofty::read_from_path;
 use std::path::Path;
 use std::borrow::Cow;
-use crate::models::AudioMetadata;
-use crate::services::SecurityValidator;
+use crate::models::{AudioFeatures, AudioMetadata, VirtualTrackPath};
+use crate::services::{CueParser, FeatureExtractor, LoudnessAnalyzer, SecurityValidator};
 
 /// Metadata extractor service - responsible for reading audio file metadata
 pub struct MetadataExtractor;
@@ -17,8 +17,12 @@ impl MetadataExtractor {
 
     /// Extract metadata from an audio file with security validation
     pub fn extract(&self, file_path: &str) -> Result<AudioMetadata, String> {
+        if let Some(virtual_track) = VirtualTrackPath::parse(file_path) {
+            return self.extract_virtual_track(&virtual_track);
+        }
+
         let path = Path::new(file_path);
-        
+
         // Validate file before processing
         SecurityValidator::validate_audio_file(path)?;
         
@@ -37,9 +41,55 @@ impl MetadataExtractor {
         let properties = tagged_file.properties();
         self.extract_properties(properties, &mut metadata);
 
+        // Gated BS.1770/EBU R128 integrated loudness, stored on the returned
+        // AudioMetadata as `integrated_lufs`. This decodes
+        // the whole file independently of the tag read above, so a failure
+        // here (e.g. a container `LoudnessAnalyzer` doesn't support) is
+        // logged and left as `None` rather than failing metadata extraction
+        // outright.
+        match LoudnessAnalyzer::new().analyze(file_path) {
+            Ok(info) => metadata.integrated_lufs = Some(info.integrated_lufs),
+            Err(e) => eprintln!("Loudness analysis failed for {}: {}", file_path, e),
+        }
+
         Ok(metadata)
     }
 
+    /// Compute a content-based feature vector (tempo, spectral shape, chroma)
+    /// for similarity search and automatic playlist sequencing. Delegates to
+    /// `FeatureExtractor`, which already implements this analysis for the
+    /// `analyze_library_features` pipeline - this method lets any caller
+    /// that's already reading tag metadata through `MetadataExtractor` opt
+    /// into feature extraction from the same place, and attach the result
+    /// via `AudioMetadata::features`.
+    pub fn extract_features(&self, file_path: &str) -> Result<AudioFeatures, String> {
+        FeatureExtractor::new().extract(file_path)
+    }
+
+    /// Extract metadata for a single CUE-carved track by re-parsing its
+    /// parent file's companion `.cue` sheet and matching the start offset
+    fn extract_virtual_track(&self, virtual_track: &VirtualTrackPath) -> Result<AudioMetadata, String> {
+        let parent_path = Path::new(&virtual_track.parent_file);
+        SecurityValidator::validate_audio_file(parent_path)?;
+
+        let cue_path = CueParser::find_companion(parent_path)
+            .ok_or_else(|| "No CUE sheet found for virtual track".to_string())?;
+
+        let tagged_file = read_from_path(parent_path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        let properties = tagged_file.properties();
+        let sample_rate = properties.sample_rate().unwrap_or(44100);
+        let total_duration = properties.duration().as_secs_f64();
+
+        let tracks = CueParser::new().parse(&cue_path, sample_rate, total_duration)?;
+
+        tracks
+            .into_iter()
+            .find(|(_, start, _)| *start == virtual_track.start_sample)
+            .map(|(metadata, _, _)| metadata)
+            .ok_or_else(|| "Virtual track no longer matches its CUE sheet".to_string())
+    }
+
     fn extract_tags(&self, tag: &Tag, metadata: &mut AudioMetadata) {
         // Extract and sanitize all text metadata
         metadata.title = tag.title()
@@ -60,6 +110,24 @@ impl MetadataExtractor {
             .map(|s: Cow<str>| SecurityValidator::sanitize_metadata(&s));
         
         metadata.track_number = tag.track();
+
+        metadata.replaygain_track_gain = tag
+            .get_string(&ItemKey::ReplayGainTrackGain)
+            .and_then(Self::parse_replaygain_db);
+        metadata.replaygain_track_peak = tag
+            .get_string(&ItemKey::ReplayGainTrackPeak)
+            .and_then(|s| s.trim().parse::<f32>().ok());
+    }
+
+    /// ReplayGain tags are conventionally stored as e.g. `"-3.20 dB"`; strip
+    /// the unit before parsing
+    fn parse_replaygain_db(raw: &str) -> Option<f32> {
+        raw.trim()
+            .trim_end_matches("dB")
+            .trim_end_matches("db")
+            .trim()
+            .parse::<f32>()
+            .ok()
     }
 
     fn extract_cover_art(&self, tag: &Tag, metadata: &mut AudioMetadata) -> Result<(), String> {
@@ -2,8 +2,23 @@ pub mod audio;
 pub mod file_scanner;
 pub mod metadata_extractor;
 pub mod security;
+pub mod feature_extractor;
+pub mod feature_cache;
+pub mod cue_parser;
+pub mod graph_factory;
+pub mod loudness_analyzer;
+pub mod tag_handler;
 
-pub use audio::{AudioDevice, DeviceManager};
-pub use file_scanner::FileScanner;
+pub use audio::{
+    AudioDevice, DeviceManager, ParameterSnapshot, Recorder, RecordingMetadata, RecordingStatus,
+    StreamManager, StreamStatus,
+};
+pub use file_scanner::{FileScanner, ScanCancellation};
 pub use metadata_extractor::MetadataExtractor;
-pub use security::SecurityValidator;
+pub use security::{AudioFormat, SecurityValidator};
+pub use feature_extractor::FeatureExtractor;
+pub use feature_cache::FeatureCache;
+pub use cue_parser::CueParser;
+pub use graph_factory::GraphFactory;
+pub use loudness_analyzer::LoudnessAnalyzer;
+pub use tag_handler::{detect_format, handler_for, TagFormat, TagHandler};
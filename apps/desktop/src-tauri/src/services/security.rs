@@ -1,5 +1,39 @@
+use std::io::Read;
 use std::path::Path;
 
+/// Audio container detected by sniffing a file's magic bytes, independent
+/// of whatever extension it happens to be named with. `Wav` carries the
+/// `fmt ` chunk's sample rate, bit depth and channel count so callers can
+/// reject out-of-range values early; the chunk's format tag is not read, so
+/// integer and float data of the same bit depth are not told apart.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AudioFormat {
+    Wav {
+        sample_rate: u32,
+        bits_per_sample: u16,
+        channels: u16,
+    },
+    Flac,
+    Ogg,
+    Mp3,
+    Mp4,
+    Aiff,
+}
+
+impl AudioFormat {
+    /// Extensions this detected format is legitimately declared under
+    fn matches_extension(&self, extension: &str) -> bool {
+        match self {
+            AudioFormat::Wav { .. } => extension == "wav",
+            AudioFormat::Flac => extension == "flac",
+            AudioFormat::Ogg => matches!(extension, "ogg" | "opus"),
+            AudioFormat::Mp3 => extension == "mp3",
+            AudioFormat::Mp4 => matches!(extension, "m4a" | "aac"),
+            AudioFormat::Aiff => extension == "aiff",
+        }
+    }
+}
+
 /// Security utilities for file validation and sanitization
 pub struct SecurityValidator;
 
@@ -30,21 +64,24 @@ impl SecurityValidator {
             return Err("Path is not a file".to_string());
         }
 
-        // Validate extension
+        // Extension is advisory, not load-bearing: a recognized one narrows
+        // the allowed-types check below, but its absence doesn't disqualify
+        // the file outright, since content sniffing is what actually decides.
         let extension = path
             .extension()
             .and_then(|e| e.to_str())
-            .ok_or("Invalid file extension")?
-            .to_lowercase();
+            .map(|e| e.to_lowercase());
 
-        if !Self::ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
-            return Err(format!("File type '{}' not allowed", extension));
+        if let Some(extension) = &extension {
+            if !Self::ALLOWED_EXTENSIONS.contains(&extension.as_str()) {
+                return Err(format!("File type '{}' not allowed", extension));
+            }
         }
 
         // Check file size
         let metadata = std::fs::metadata(path)
             .map_err(|e| format!("Failed to read file metadata: {}", e))?;
-        
+
         if metadata.len() > Self::MAX_AUDIO_FILE_SIZE {
             return Err("File size exceeds maximum allowed (500 MB)".to_string());
         }
@@ -52,6 +89,104 @@ impl SecurityValidator {
         // Prevent path traversal
         Self::validate_path_safety(path)?;
 
+        // Don't trust the extension: sniff the actual container. When there
+        // is an extension, make sure it's the one the sniffed format claims
+        // to be, catching renamed or truncated files before they reach the
+        // decoder. When there is none, a successful sniff is enough on its
+        // own to admit the file.
+        let format = Self::detect_format(path)?;
+        if let Some(extension) = &extension {
+            if !format.matches_extension(extension) {
+                return Err(format!(
+                    "File extension '.{}' doesn't match its detected format ({:?})",
+                    extension, format
+                ));
+            }
+        }
+
+        if let AudioFormat::Wav { sample_rate, bits_per_sample, channels } = format {
+            Self::validate_wav_pcm(sample_rate, bits_per_sample, channels)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sniff `path`'s magic bytes and recognize its container, regardless of
+    /// its extension. Reads only a small header, not the whole file.
+    pub fn detect_format(path: &Path) -> Result<AudioFormat, String> {
+        let mut file = std::fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+        let mut header = [0u8; 128];
+        let read = file
+            .read(&mut header)
+            .map_err(|e| format!("Failed to read file header: {}", e))?;
+        let header = &header[..read];
+
+        if header.len() >= 12 && &header[0..4] == b"RIFF" && &header[8..12] == b"WAVE" {
+            return Self::parse_wav_fmt_chunk(header);
+        }
+        if header.len() >= 4 && &header[0..4] == b"fLaC" {
+            return Ok(AudioFormat::Flac);
+        }
+        if header.len() >= 4 && &header[0..4] == b"OggS" {
+            return Ok(AudioFormat::Ogg);
+        }
+        if header.len() >= 12 && &header[4..8] == b"ftyp" {
+            return Ok(AudioFormat::Mp4);
+        }
+        if header.len() >= 12 && &header[0..4] == b"FORM" && &header[8..12] == b"AIFF" {
+            return Ok(AudioFormat::Aiff);
+        }
+        if header.len() >= 3 && &header[0..3] == b"ID3" {
+            return Ok(AudioFormat::Mp3);
+        }
+        if header.len() >= 2 && header[0] == 0xFF && (header[1] & 0xE0) == 0xE0 {
+            return Ok(AudioFormat::Mp3);
+        }
+
+        Err("Unrecognized audio file signature".to_string())
+    }
+
+    /// Walk a WAVE file's chunks (skipping any that precede `fmt `, e.g. a
+    /// `JUNK` padding chunk) to pull out sample rate, bit depth, and channel
+    /// count.
+    fn parse_wav_fmt_chunk(header: &[u8]) -> Result<AudioFormat, String> {
+        let mut offset = 12;
+        while offset + 8 <= header.len() {
+            let chunk_id = &header[offset..offset + 4];
+            let chunk_size = u32::from_le_bytes(
+                header[offset + 4..offset + 8].try_into().map_err(|_| "Malformed WAV chunk header")?,
+            ) as usize;
+
+            if chunk_id == b"fmt " {
+                let data = offset + 8;
+                if data + 16 > header.len() {
+                    return Err("Truncated WAV fmt chunk".to_string());
+                }
+                let channels = u16::from_le_bytes(header[data + 2..data + 4].try_into().unwrap());
+                let sample_rate = u32::from_le_bytes(header[data + 4..data + 8].try_into().unwrap());
+                let bits_per_sample = u16::from_le_bytes(header[data + 14..data + 16].try_into().unwrap());
+                return Ok(AudioFormat::Wav { sample_rate, bits_per_sample, channels });
+            }
+
+            // Chunks are word-aligned: an odd-sized chunk has a padding byte
+            offset += 8 + chunk_size + (chunk_size % 2);
+        }
+
+        Err("WAV file is missing a fmt chunk".to_string())
+    }
+
+    /// Reject PCM variants the decoder can't handle, before they're ever
+    /// handed to it
+    fn validate_wav_pcm(sample_rate: u32, bits_per_sample: u16, channels: u16) -> Result<(), String> {
+        if sample_rate == 0 || sample_rate > 384_000 {
+            return Err(format!("Unsupported WAV sample rate: {} Hz", sample_rate));
+        }
+        if !matches!(bits_per_sample, 8 | 16 | 24 | 32) {
+            return Err(format!("Unsupported WAV bit depth: {}-bit", bits_per_sample));
+        }
+        if channels == 0 || channels > 32 {
+            return Err(format!("Unsupported WAV channel count: {}", channels));
+        }
         Ok(())
     }
 
@@ -134,8 +269,53 @@ impl SecurityValidator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Write;
     use std::path::PathBuf;
 
+    fn write_temp_file(name: &str, contents: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_detect_format_recognizes_flac_magic_bytes() {
+        let path = write_temp_file("sonantica_test_detect_flac.flac", b"fLaC\x00\x00\x00\x00");
+        assert_eq!(SecurityValidator::detect_format(&path), Ok(AudioFormat::Flac));
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_detect_format_parses_wav_fmt_chunk() {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&36u32.to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&2u16.to_le_bytes()); // channels
+        wav.extend_from_slice(&44100u32.to_le_bytes()); // sample rate
+        wav.extend_from_slice(&176400u32.to_le_bytes()); // byte rate
+        wav.extend_from_slice(&4u16.to_le_bytes()); // block align
+        wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+
+        let path = write_temp_file("sonantica_test_detect_wav.wav", &wav);
+        assert_eq!(
+            SecurityValidator::detect_format(&path),
+            Ok(AudioFormat::Wav { sample_rate: 44100, bits_per_sample: 16, channels: 2 })
+        );
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_detect_format_rejects_unrecognized_content() {
+        let path = write_temp_file("sonantica_test_detect_bogus.mp3", b"not actually audio data");
+        assert!(SecurityValidator::detect_format(&path).is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
     #[test]
     fn test_sanitize_metadata() {
         assert_eq!(
@@ -0,0 +1,196 @@
+use std::path::Path;
+
+use lofty::config::WriteOptions;
+use lofty::file::{AudioFile, FileType, TaggedFileExt};
+use lofty::probe::Probe;
+use lofty::tag::{Accessor, ItemKey, Tag, TagType};
+
+use crate::models::AudioMetadata;
+use crate::services::SecurityValidator;
+
+/// Tag container family a file actually uses, determined by sniffing its
+/// contents rather than trusting the extension (a renamed `.mp3` that's
+/// really a `.m4a` shouldn't get parsed as ID3).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagFormat {
+    /// ID3v2 frames (mp3, aiff, wav)
+    Id3v2,
+    /// Vorbis comments (flac, ogg, opus)
+    VorbisComments,
+    /// MP4 atoms / iTunes-style `ilst` (m4a, aac)
+    Mp4,
+}
+
+/// Sniff `path`'s actual container format and map it to the tag family that
+/// owns writing its metadata.
+pub fn detect_format(path: &Path) -> Result<TagFormat, String> {
+    let probe = Probe::open(path)
+        .map_err(|e| format!("Failed to open file for format detection: {}", e))?
+        .guess_file_type()
+        .map_err(|e| format!("Failed to detect file format: {}", e))?;
+
+    match probe.file_type() {
+        Some(FileType::Mpeg) | Some(FileType::Aiff) | Some(FileType::Wav) => Ok(TagFormat::Id3v2),
+        Some(FileType::Flac) | Some(FileType::Vorbis) | Some(FileType::Opus) | Some(FileType::Speex) => {
+            Ok(TagFormat::VorbisComments)
+        }
+        Some(FileType::Mp4) => Ok(TagFormat::Mp4),
+        Some(other) => Err(format!("Unsupported tag format: {:?}", other)),
+        None => Err("Could not determine file format".to_string()),
+    }
+}
+
+/// Reads and writes an `AudioMetadata` for one tag container family. The
+/// command layer stays format-agnostic by going through `detect_format`
+/// and this trait; each implementation only needs to know its own quirks
+/// (e.g. where cover art lives in that container).
+pub trait TagHandler {
+    fn read(&self, path: &Path) -> Result<AudioMetadata, String>;
+    fn write(&self, path: &Path, metadata: &AudioMetadata) -> Result<(), String>;
+}
+
+/// Resolve the `TagHandler` for `path` by sniffing its format
+pub fn handler_for(path: &Path) -> Result<Box<dyn TagHandler>, String> {
+    match detect_format(path)? {
+        TagFormat::Id3v2 => Ok(Box::new(Id3v2Handler)),
+        TagFormat::VorbisComments => Ok(Box::new(VorbisCommentHandler)),
+        TagFormat::Mp4 => Ok(Box::new(Mp4Handler)),
+    }
+}
+
+/// Read every common text/cover field lofty exposes through its unified
+/// `Tag` API; the three handlers below differ only in where they persist
+/// the result (APIC frame, FLAC `PICTURE` block, or `covr` atom), which
+/// lofty already takes care of once the tag type is chosen correctly.
+fn read_common(path: &Path) -> Result<AudioMetadata, String> {
+    let tagged_file = lofty::read_from_path(path).map_err(|e| format!("Failed to read file: {}", e))?;
+    let mut metadata = AudioMetadata::default();
+
+    if let Some(tag) = tagged_file.primary_tag().or_else(|| tagged_file.first_tag()) {
+        metadata.title = tag.title().map(|s| SecurityValidator::sanitize_metadata(&s));
+        metadata.artist = tag.artist().map(|s| SecurityValidator::sanitize_metadata(&s));
+        metadata.album = tag.album().map(|s| SecurityValidator::sanitize_metadata(&s));
+        metadata.album_artist = tag
+            .get_string(&ItemKey::AlbumArtist)
+            .map(SecurityValidator::sanitize_metadata);
+        metadata.year = tag.year();
+        metadata.genre = tag.genre().map(|s| SecurityValidator::sanitize_metadata(&s));
+        metadata.track_number = tag.track();
+
+        if let Some(picture) = tag.pictures().first() {
+            let data = picture.data();
+            SecurityValidator::validate_cover_art(data)?;
+            let mime = picture.mime_type().map(|m| m.as_str()).unwrap_or("image/jpeg");
+            let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, data);
+            metadata.cover_art = Some(format!("data:{};base64,{}", mime, encoded));
+        }
+    }
+
+    let properties = tagged_file.properties();
+    metadata.duration = Some(properties.duration().as_secs_f64());
+    metadata.bitrate = properties.audio_bitrate();
+    metadata.sample_rate = properties.sample_rate();
+
+    Ok(metadata)
+}
+
+/// Sanitize every string field and validate any embedded cover art, then
+/// write it into `tag_type`'s tag (creating one if the file doesn't have it
+/// yet) and save the file back out.
+fn write_common(path: &Path, metadata: &AudioMetadata, tag_type: TagType) -> Result<(), String> {
+    let mut tagged_file = lofty::read_from_path(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    if tagged_file.tag(tag_type).is_none() {
+        tagged_file.insert_tag(Tag::new(tag_type));
+    }
+    let tag = tagged_file
+        .tag_mut(tag_type)
+        .ok_or_else(|| "File has no writable tag".to_string())?;
+
+    if let Some(title) = &metadata.title {
+        tag.set_title(SecurityValidator::sanitize_metadata(title));
+    }
+    if let Some(artist) = &metadata.artist {
+        tag.set_artist(SecurityValidator::sanitize_metadata(artist));
+    }
+    if let Some(album) = &metadata.album {
+        tag.set_album(SecurityValidator::sanitize_metadata(album));
+    }
+    if let Some(album_artist) = &metadata.album_artist {
+        tag.insert_text(ItemKey::AlbumArtist, SecurityValidator::sanitize_metadata(album_artist));
+    }
+    if let Some(year) = metadata.year {
+        tag.set_year(year);
+    }
+    if let Some(genre) = &metadata.genre {
+        tag.set_genre(SecurityValidator::sanitize_metadata(genre));
+    }
+    if let Some(track_number) = metadata.track_number {
+        tag.set_track(track_number);
+    }
+
+    if let Some(cover_art) = &metadata.cover_art {
+        let raw = decode_cover_art(cover_art)?;
+        SecurityValidator::validate_cover_art(&raw)?;
+        tag.set_picture(0, lofty::picture::Picture::from_reader(&mut raw.as_slice())
+            .map_err(|e| format!("Failed to decode cover art: {}", e))?);
+    }
+
+    tagged_file
+        .save_to_path(path, WriteOptions::default())
+        .map_err(|e| format!("Failed to save tags: {}", e))
+}
+
+/// `cover_art` is carried around as a `data:<mime>;base64,<data>` URI (see
+/// `MetadataExtractor::extract_cover_art`); pull the raw bytes back out.
+fn decode_cover_art(data_uri: &str) -> Result<Vec<u8>, String> {
+    let encoded = data_uri
+        .split_once("base64,")
+        .map(|(_, rest)| rest)
+        .ok_or_else(|| "Cover art is not a base64 data URI".to_string())?;
+
+    base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded)
+        .map_err(|e| format!("Failed to decode cover art: {}", e))
+}
+
+/// ID3v2 frames: mp3, aiff, wav
+struct Id3v2Handler;
+
+impl TagHandler for Id3v2Handler {
+    fn read(&self, path: &Path) -> Result<AudioMetadata, String> {
+        read_common(path)
+    }
+
+    fn write(&self, path: &Path, metadata: &AudioMetadata) -> Result<(), String> {
+        write_common(path, metadata, TagType::Id3v2)
+    }
+}
+
+/// Vorbis comments: flac, ogg, opus. Cover art lives in a FLAC `PICTURE`
+/// metadata block / Vorbis comment `METADATA_BLOCK_PICTURE`, which lofty's
+/// `Tag::set_picture` already targets correctly for this tag type.
+struct VorbisCommentHandler;
+
+impl TagHandler for VorbisCommentHandler {
+    fn read(&self, path: &Path) -> Result<AudioMetadata, String> {
+        read_common(path)
+    }
+
+    fn write(&self, path: &Path, metadata: &AudioMetadata) -> Result<(), String> {
+        write_common(path, metadata, TagType::VorbisComments)
+    }
+}
+
+/// MP4 atoms / iTunes-style `ilst`: m4a, aac. Cover art lives in the `covr`
+/// atom instead of an APIC/PICTURE frame.
+struct Mp4Handler;
+
+impl TagHandler for Mp4Handler {
+    fn read(&self, path: &Path) -> Result<AudioMetadata, String> {
+        read_common(path)
+    }
+
+    fn write(&self, path: &Path, metadata: &AudioMetadata) -> Result<(), String> {
+        write_common(path, metadata, TagType::Mp4Ilst)
+    }
+}
@@ -46,7 +46,11 @@ impl AudioNode for CompressorNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "CompressorNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
         NodeMetadata {
             name: "Compressor".to_string(),
@@ -0,0 +1,207 @@
+use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
+use nnnoiseless::DenoiseState;
+
+/// `nnnoiseless`/RNNoise operates on fixed 480-sample frames at 48 kHz
+const FRAME_SIZE: usize = 480;
+
+/// The only sample rate `nnnoiseless`'s model was trained for; feeding it
+/// frames from any other rate would silently produce wrong VAD
+/// probabilities instead of an error
+const REQUIRED_SAMPLE_RATE: u32 = 48000;
+
+/// RNNoise expects samples on the same scale as 16-bit PCM, not our usual -1.0..1.0 float range
+const PCM_SCALE: f32 = 32768.0;
+
+/// Neural noise suppression node, wrapping `nnnoiseless`'s RNNoise model
+///
+/// The denoiser only accepts exactly `FRAME_SIZE` samples at a time, so each
+/// channel gets its own `DenoiseState` plus an accumulator: incoming samples
+/// are deinterleaved and appended to their channel's accumulator, full
+/// frames are drained and denoised, and any leftover samples (fewer than a
+/// full frame) carry over to the next `process()` call. Output length
+/// therefore doesn't always match input length in a given call - the tail
+/// catches up once enough audio has accumulated, the same tradeoff
+/// `ResamplerNode` makes.
+pub struct DenoiseNode {
+    id: String,
+    vad_threshold: f32,
+    channels: usize,
+    states: Vec<Box<DenoiseState<'static>>>,
+    accumulators: Vec<Vec<f32>>,
+}
+
+impl DenoiseNode {
+    /// Create a new denoiser node
+    pub fn new(id: String) -> Self {
+        Self {
+            id,
+            vad_threshold: 0.0,
+            channels: 0,
+            states: Vec::new(),
+            accumulators: Vec::new(),
+        }
+    }
+
+    fn ensure_channels(&mut self, channels: usize) {
+        if self.channels != channels {
+            self.channels = channels;
+            self.states = (0..channels).map(|_| DenoiseState::new()).collect();
+            self.accumulators = vec![Vec::new(); channels];
+        }
+    }
+}
+
+impl AudioNode for DenoiseNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> &str {
+        "DenoiseNode"
+    }
+
+    fn metadata(&self) -> NodeMetadata {
+        NodeMetadata {
+            name: "Denoiser".to_string(),
+            category: NodeCategory::Effect,
+            input_channels: 2,
+            output_channels: 2,
+            parameters: vec![
+                ParameterDescriptor::new(
+                    "vad_threshold",
+                    0.0,
+                    1.0,
+                    0.0,
+                    "",
+                    "Voice Gate",
+                ),
+            ],
+            plugin: "compositor".to_string(),
+        }
+    }
+
+    fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+        if input.sample_rate != REQUIRED_SAMPLE_RATE {
+            return Err(GraphError::SampleRateMismatch {
+                expected: REQUIRED_SAMPLE_RATE,
+                actual: input.sample_rate,
+            });
+        }
+
+        self.ensure_channels(input.channels);
+
+        let frames = input.num_frames();
+        for frame in 0..frames {
+            for ch in 0..input.channels {
+                let sample = input.samples[frame * input.channels + ch];
+                self.accumulators[ch].push(sample * PCM_SCALE);
+            }
+        }
+
+        let mut cleaned: Vec<Vec<f32>> = vec![Vec::new(); input.channels];
+        for ch in 0..input.channels {
+            let mut drained = 0;
+            while self.accumulators[ch].len() - drained >= FRAME_SIZE {
+                let in_frame = &self.accumulators[ch][drained..drained + FRAME_SIZE];
+                let mut out_frame = vec![0.0f32; FRAME_SIZE];
+                let vad_probability = self.states[ch].process_frame(in_frame, &mut out_frame);
+
+                if vad_probability < self.vad_threshold {
+                    out_frame.iter_mut().for_each(|s| *s = 0.0);
+                }
+
+                cleaned[ch].extend(out_frame.iter().map(|s| s / PCM_SCALE));
+                drained += FRAME_SIZE;
+            }
+            self.accumulators[ch].drain(0..drained);
+        }
+
+        let out_frames = cleaned.iter().map(|c| c.len()).min().unwrap_or(0);
+        let mut output = AudioBuffer::new(input.channels, input.sample_rate, out_frames);
+        for frame in 0..out_frames {
+            for ch in 0..input.channels {
+                output.samples[frame * input.channels + ch] = cleaned[ch][frame];
+            }
+        }
+
+        Ok(output)
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+        match name {
+            "vad_threshold" => self.vad_threshold = value.clamp(0.0, 1.0),
+            _ => return Err(GraphError::ParameterNotFound(name.to_string())),
+        }
+        Ok(())
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        match name {
+            "vad_threshold" => Some(self.vad_threshold),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        self.states = (0..self.channels).map(|_| DenoiseState::new()).collect();
+        self.accumulators = vec![Vec::new(); self.channels];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_denoise_creation_defaults() {
+        let node = DenoiseNode::new("denoise1".to_string());
+        assert_eq!(node.get_parameter("vad_threshold"), Some(0.0));
+    }
+
+    #[test]
+    fn test_denoise_buffers_partial_frames() {
+        let mut node = DenoiseNode::new("denoise1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 100);
+        input.samples.iter_mut().enumerate().for_each(|(i, s)| *s = 0.2 * (i as f32 * 0.05).sin());
+
+        let output = node.process(&input).unwrap();
+        assert_eq!(output.num_frames(), 0);
+    }
+
+    #[test]
+    fn test_denoise_emits_once_frame_is_full() {
+        let mut node = DenoiseNode::new("denoise1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, FRAME_SIZE);
+        input.samples.iter_mut().enumerate().for_each(|(i, s)| *s = 0.2 * (i as f32 * 0.05).sin());
+
+        let output = node.process(&input).unwrap();
+        assert_eq!(output.num_frames(), FRAME_SIZE);
+    }
+
+    #[test]
+    fn test_denoise_rejects_non_48k_input() {
+        let mut node = DenoiseNode::new("denoise1".to_string());
+
+        let input = AudioBuffer::new(2, 44100, 100);
+        let result = node.process(&input);
+
+        assert!(matches!(
+            result,
+            Err(GraphError::SampleRateMismatch { expected: 48000, actual: 44100 })
+        ));
+    }
+
+    #[test]
+    fn test_denoise_reset_clears_accumulators() {
+        let mut node = DenoiseNode::new("denoise1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 100);
+        input.samples.iter_mut().for_each(|s| *s = 0.1);
+        node.process(&input).unwrap();
+
+        node.reset();
+        assert!(node.accumulators.iter().all(|a| a.is_empty()));
+    }
+}
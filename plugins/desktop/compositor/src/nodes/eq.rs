@@ -2,7 +2,7 @@ use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDesc
 use std::f32::consts::PI;
 
 /// Parametric equalizer node with multiple bands
-/// 
+///
 /// This node demonstrates more complex audio processing that can work
 /// in conjunction with other plugins (e.g., Orquestador routing).
 pub struct EqualizerNode {
@@ -15,21 +15,68 @@ struct EQBand {
     frequency: f32,
     gain_db: f32,
     q: f32,
+    filter_type: FilterType,
     filter: BiquadFilter,
 }
 
+/// Shape of a single EQ band, following the RBJ Audio EQ Cookbook
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum FilterType {
+    /// Classic bell/peaking filter (the historical default for this node)
+    Peaking,
+    LowPass,
+    HighPass,
+    BandPass,
+    Notch,
+    AllPass,
+    LowShelf,
+    HighShelf,
+}
+
+impl FilterType {
+    /// Map a `filter_type` parameter value to its enum variant
+    ///
+    /// Values match the order above (0 = Peaking .. 7 = HighShelf), so the
+    /// parameter can be driven directly from `ParameterDescriptor` min/max/default.
+    fn from_param(value: f32) -> Self {
+        match value.round() as i32 {
+            1 => FilterType::LowPass,
+            2 => FilterType::HighPass,
+            3 => FilterType::BandPass,
+            4 => FilterType::Notch,
+            5 => FilterType::AllPass,
+            6 => FilterType::LowShelf,
+            7 => FilterType::HighShelf,
+            _ => FilterType::Peaking,
+        }
+    }
+
+    fn to_param(self) -> f32 {
+        match self {
+            FilterType::Peaking => 0.0,
+            FilterType::LowPass => 1.0,
+            FilterType::HighPass => 2.0,
+            FilterType::BandPass => 3.0,
+            FilterType::Notch => 4.0,
+            FilterType::AllPass => 5.0,
+            FilterType::LowShelf => 6.0,
+            FilterType::HighShelf => 7.0,
+        }
+    }
+}
+
 impl EqualizerNode {
     /// Create a new parametric EQ with the specified number of bands
-    /// 
+    ///
     /// # Arguments
     /// * `id` - Unique identifier
     /// * `num_bands` - Number of EQ bands (typically 3, 5, or 10)
     pub fn new(id: String, num_bands: usize) -> Self {
         let default_frequencies = [
-            60.0, 170.0, 310.0, 600.0, 1000.0, 
+            60.0, 170.0, 310.0, 600.0, 1000.0,
             3000.0, 6000.0, 12000.0, 14000.0, 16000.0
         ];
-        
+
         let mut bands = Vec::new();
         for i in 0..num_bands {
             let freq = if i < default_frequencies.len() {
@@ -37,26 +84,38 @@ impl EqualizerNode {
             } else {
                 1000.0
             };
-            
+
+            // The standard channel-EQ layout: the first band shelves low,
+            // the last band shelves high, everything in between is a bell.
+            let filter_type = if i == 0 {
+                FilterType::LowShelf
+            } else if i == num_bands - 1 && num_bands > 1 {
+                FilterType::HighShelf
+            } else {
+                FilterType::Peaking
+            };
+
             bands.push(EQBand {
                 frequency: freq,
                 gain_db: 0.0,
                 q: 1.0,
+                filter_type,
                 filter: BiquadFilter::new(),
             });
         }
-        
+
         Self {
             id,
             bands,
             sample_rate: 48000.0,
         }
     }
-    
+
     /// Update all filter coefficients
     fn update_filters(&mut self) {
         for band in &mut self.bands {
-            band.filter.update_peaking_eq(
+            band.filter.update(
+                band.filter_type,
                 band.frequency,
                 band.gain_db,
                 band.q,
@@ -70,10 +129,14 @@ impl AudioNode for EqualizerNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "EqualizerNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
         let mut parameters = Vec::new();
-        
+
         for (i, band) in self.bands.iter().enumerate() {
             parameters.push(ParameterDescriptor::new(
                 &format!("band_{}_gain", i),
@@ -83,7 +146,7 @@ impl AudioNode for EqualizerNode {
                 "dB",
                 &format!("Band {} Gain ({:.0} Hz)", i + 1, band.frequency),
             ));
-            
+
             parameters.push(ParameterDescriptor::new(
                 &format!("band_{}_freq", i),
                 20.0,
@@ -92,7 +155,7 @@ impl AudioNode for EqualizerNode {
                 "Hz",
                 &format!("Band {} Frequency", i + 1),
             ));
-            
+
             parameters.push(ParameterDescriptor::new(
                 &format!("band_{}_q", i),
                 0.1,
@@ -101,8 +164,17 @@ impl AudioNode for EqualizerNode {
                 "",
                 &format!("Band {} Q", i + 1),
             ));
+
+            parameters.push(ParameterDescriptor::new(
+                &format!("band_{}_type", i),
+                0.0,
+                7.0,
+                band.filter_type.to_param(),
+                "",
+                &format!("Band {} Type", i + 1),
+            ));
         }
-        
+
         NodeMetadata {
             name: format!("{}-Band Parametric EQ", self.bands.len()),
             category: NodeCategory::Effect,
@@ -112,38 +184,42 @@ impl AudioNode for EqualizerNode {
             plugin: "compositor".to_string(),
         }
     }
-    
+
     fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
         let mut output = input.clone();
-        
-        // Apply each band sequentially
+
+        // Apply each band sequentially. Every non-peaking band always runs
+        // (pass, notch and all-pass shapes alter the signal at any gain; a
+        // shelf at 0 dB is flat but runs too), peaking bands are skipped when
+        // their gain is negligible as a minor optimization.
         for band in &mut self.bands {
-            if band.gain_db.abs() > 0.01 {  // Only process if gain is significant
+            let always_active = !matches!(band.filter_type, FilterType::Peaking);
+            if always_active || band.gain_db.abs() > 0.01 {
                 band.filter.process_stereo(&mut output.samples);
             }
         }
-        
+
         Ok(output)
     }
-    
+
     fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
         // Parse parameter name (e.g., "band_0_gain", "band_1_freq")
         let parts: Vec<&str> = name.split('_').collect();
-        
+
         if parts.len() != 3 || parts[0] != "band" {
             return Err(GraphError::ParameterNotFound(name.to_string()));
         }
-        
+
         let band_idx: usize = parts[1].parse()
             .map_err(|_| GraphError::ParameterNotFound(name.to_string()))?;
-        
+
         if band_idx >= self.bands.len() {
             return Err(GraphError::ParameterNotFound(name.to_string()));
         }
-        
+
         let param_type = parts[2];
         let band = &mut self.bands[band_idx];
-        
+
         match param_type {
             "gain" => {
                 band.gain_db = value.clamp(-24.0, 24.0);
@@ -154,36 +230,40 @@ impl AudioNode for EqualizerNode {
             "q" => {
                 band.q = value.clamp(0.1, 10.0);
             }
+            "type" => {
+                band.filter_type = FilterType::from_param(value);
+            }
             _ => return Err(GraphError::ParameterNotFound(name.to_string())),
         }
-        
+
         self.update_filters();
         Ok(())
     }
-    
+
     fn get_parameter(&self, name: &str) -> Option<f32> {
         let parts: Vec<&str> = name.split('_').collect();
-        
+
         if parts.len() != 3 || parts[0] != "band" {
             return None;
         }
-        
+
         let band_idx: usize = parts[1].parse().ok()?;
-        
+
         if band_idx >= self.bands.len() {
             return None;
         }
-        
+
         let band = &self.bands[band_idx];
-        
+
         match parts[2] {
             "gain" => Some(band.gain_db),
             "freq" => Some(band.frequency),
             "q" => Some(band.q),
+            "type" => Some(band.filter_type.to_param()),
             _ => None,
         }
     }
-    
+
     fn reset(&mut self) {
         for band in &mut self.bands {
             band.filter.reset();
@@ -191,22 +271,21 @@ impl AudioNode for EqualizerNode {
     }
 }
 
-/// Biquad filter implementation (Peaking EQ)
-/// Based on RBJ Audio EQ Cookbook
+/// Biquad filter implementation covering the full RBJ Audio EQ Cookbook family
 struct BiquadFilter {
-    // Filter coefficients
+    // Filter coefficients (normalized so a0 == 1)
     a0: f32,
     a1: f32,
     a2: f32,
     b1: f32,
     b2: f32,
-    
+
     // State variables (for left channel)
     x1_l: f32,
     x2_l: f32,
     y1_l: f32,
     y2_l: f32,
-    
+
     // State variables (for right channel)
     x1_r: f32,
     x2_r: f32,
@@ -232,27 +311,154 @@ impl BiquadFilter {
             y2_r: 0.0,
         }
     }
-    
+
+    /// Recompute coefficients for the given filter shape
+    fn update(&mut self, filter_type: FilterType, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        match filter_type {
+            FilterType::Peaking => self.update_peaking_eq(freq, gain_db, q, sample_rate),
+            FilterType::LowPass => self.update_low_pass(freq, q, sample_rate),
+            FilterType::HighPass => self.update_high_pass(freq, q, sample_rate),
+            FilterType::BandPass => self.update_band_pass(freq, q, sample_rate),
+            FilterType::Notch => self.update_notch(freq, q, sample_rate),
+            FilterType::AllPass => self.update_all_pass(freq, q, sample_rate),
+            FilterType::LowShelf => self.update_low_shelf(freq, gain_db, q, sample_rate),
+            FilterType::HighShelf => self.update_high_shelf(freq, gain_db, q, sample_rate),
+        }
+    }
+
+    fn set_coeffs(&mut self, b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) {
+        self.a0 = b0 / a0;
+        self.a1 = b1 / a0;
+        self.a2 = b2 / a0;
+        self.b1 = a1 / a0;
+        self.b2 = a2 / a0;
+    }
+
     fn update_peaking_eq(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
         let w0 = 2.0 * PI * freq / sample_rate;
         let alpha = w0.sin() / (2.0 * q);
         let a = 10.0_f32.powf(gain_db / 40.0);
-        
+
         let b0 = 1.0 + alpha * a;
         let b1 = -2.0 * w0.cos();
         let b2 = 1.0 - alpha * a;
         let a0 = 1.0 + alpha / a;
         let a1 = -2.0 * w0.cos();
         let a2 = 1.0 - alpha / a;
-        
-        // Normalize
-        self.a0 = b0 / a0;
-        self.a1 = b1 / a0;
-        self.a2 = b2 / a0;
-        self.b1 = a1 / a0;
-        self.b2 = a2 / a0;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_low_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = (1.0 - cos_w0) / 2.0;
+        let b1 = 1.0 - cos_w0;
+        let b2 = (1.0 - cos_w0) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_high_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = (1.0 + cos_w0) / 2.0;
+        let b1 = -(1.0 + cos_w0);
+        let b2 = (1.0 + cos_w0) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_band_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        // Constant 0 dB peak gain variant
+        let b0 = alpha;
+        let b1 = 0.0;
+        let b2 = -alpha;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
     }
-    
+
+    fn update_notch(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = 1.0;
+        let b1 = -2.0 * cos_w0;
+        let b2 = 1.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_all_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = 1.0 - alpha;
+        let b1 = -2.0 * cos_w0;
+        let b2 = 1.0 + alpha;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_low_shelf(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let alpha = w0.sin() / (2.0 * q);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let b0 = a * ((a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+        let b1 = 2.0 * a * ((a - 1.0) - (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+        let a0 = (a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+        let a1 = -2.0 * ((a - 1.0) + (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_high_shelf(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let alpha = w0.sin() / (2.0 * q);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let b0 = a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+        let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+        let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
     fn process_stereo(&mut self, samples: &mut [f32]) {
         // Process interleaved stereo samples [L, R, L, R, ...]
         for i in (0..samples.len()).step_by(2) {
@@ -260,30 +466,30 @@ impl BiquadFilter {
             let x_l = samples[i];
             let y_l = self.a0 * x_l + self.a1 * self.x1_l + self.a2 * self.x2_l
                     - self.b1 * self.y1_l - self.b2 * self.y2_l;
-            
+
             self.x2_l = self.x1_l;
             self.x1_l = x_l;
             self.y2_l = self.y1_l;
             self.y1_l = y_l;
-            
+
             samples[i] = y_l;
-            
+
             // Right channel
             if i + 1 < samples.len() {
                 let x_r = samples[i + 1];
                 let y_r = self.a0 * x_r + self.a1 * self.x1_r + self.a2 * self.x2_r
                         - self.b1 * self.y1_r - self.b2 * self.y2_r;
-                
+
                 self.x2_r = self.x1_r;
                 self.x1_r = x_r;
                 self.y2_r = self.y1_r;
                 self.y1_r = y_r;
-                
+
                 samples[i + 1] = y_r;
             }
         }
     }
-    
+
     fn reset(&mut self) {
         self.x1_l = 0.0;
         self.x2_l = 0.0;
@@ -299,38 +505,57 @@ impl BiquadFilter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_eq_creation() {
         let eq = EqualizerNode::new("eq1".to_string(), 5);
         assert_eq!(eq.bands.len(), 5);
         assert_eq!(eq.id(), "eq1");
     }
-    
+
     #[test]
     fn test_eq_parameters() {
         let mut eq = EqualizerNode::new("eq1".to_string(), 3);
-        
+
         // Set band 0 gain to +6 dB
         eq.set_parameter("band_0_gain", 6.0).unwrap();
         assert_eq!(eq.get_parameter("band_0_gain"), Some(6.0));
-        
+
         // Set band 1 frequency to 1000 Hz
         eq.set_parameter("band_1_freq", 1000.0).unwrap();
         assert_eq!(eq.get_parameter("band_1_freq"), Some(1000.0));
-        
+
         // Set band 2 Q to 2.0
         eq.set_parameter("band_2_q", 2.0).unwrap();
         assert_eq!(eq.get_parameter("band_2_q"), Some(2.0));
     }
-    
+
+    #[test]
+    fn test_eq_default_shapes() {
+        let eq = EqualizerNode::new("eq1".to_string(), 3);
+
+        // First/last bands shelve, middle band is a bell
+        assert_eq!(eq.bands[0].filter_type, FilterType::LowShelf);
+        assert_eq!(eq.bands[1].filter_type, FilterType::Peaking);
+        assert_eq!(eq.bands[2].filter_type, FilterType::HighShelf);
+    }
+
+    #[test]
+    fn test_eq_set_filter_type() {
+        let mut eq = EqualizerNode::new("eq1".to_string(), 3);
+
+        eq.set_parameter("band_1_type", 2.0).unwrap(); // HighPass
+        assert_eq!(eq.get_parameter("band_1_type"), Some(2.0));
+        assert_eq!(eq.bands[1].filter_type, FilterType::HighPass);
+    }
+
     #[test]
     fn test_eq_processing() {
         let mut eq = EqualizerNode::new("eq1".to_string(), 3);
-        
+
         let input = AudioBuffer::new(2, 48000, 512);
         let output = eq.process(&input).unwrap();
-        
+
         assert_eq!(output.channels, 2);
         assert_eq!(output.sample_rate, 48000);
     }
@@ -38,7 +38,11 @@ impl AudioNode for GainNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "GainNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
         NodeMetadata {
             name: "Gain".to_string(),
@@ -0,0 +1,449 @@
+use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError, SmoothedParameter};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+/// 400 ms analysis block, stepped every 100 ms hop (75% overlap), per BS.1770
+const BLOCK_HOPS: usize = 4;
+const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+const RELATIVE_GATE_OFFSET: f32 = -10.0;
+const OVERSAMPLE_FACTOR: usize = 4;
+
+/// Ramp time for the correction gain, which only updates once per 100 ms
+/// hop: a one-pole time constant of one full hop carries the gain about 63%
+/// of the way toward a freshly measured correction before the next update
+/// lands, so it glides rather than stepping discretely like a zipper
+const DEFAULT_GAIN_SMOOTHING_MS: f32 = 100.0;
+
+/// EBU R128 loudness normalization node
+///
+/// Measures integrated loudness in real time (K-weighted, two-stage gated
+/// per BS.1770) and applies the gain offset needed to bring the signal to
+/// `target` LUFS, then brickwall-limits the result so the oversampled true
+/// peak never exceeds `max_true_peak`. Because measurement runs on the same
+/// stream it corrects, the applied gain converges as more audio arrives
+/// rather than being computed from a full two-pass analysis; `range` is a
+/// deadband (in LU) around `target` so the correction doesn't hunt once
+/// it's close. The gain itself is smoothed (see `SmoothedParameter`): it
+/// only retargets once per 100 ms hop, but ramps toward that target sample
+/// by sample so it never steps abruptly.
+pub struct LoudnessNormNode {
+    id: String,
+    target_lufs: f32,
+    range_lu: f32,
+    max_true_peak_db: f32,
+
+    sample_rate: f32,
+    k_weight: Vec<KWeightingFilter>,
+
+    hop_accumulator: f32,
+    hop_samples_seen: usize,
+    hop_size: usize,
+
+    /// Weighted sum-of-squares of the last `BLOCK_HOPS` hops (a sliding
+    /// 400 ms window stepped every 100 ms)
+    recent_hops: VecDeque<f32>,
+    /// One windowed-block energy per hop step, used for integrated gating
+    gating_blocks: Vec<f32>,
+
+    measured_integrated_lufs: f32,
+    /// Correction gain (linear), smoothed so the hop-rate measurement
+    /// updates don't step discretely into the output
+    gain: SmoothedParameter,
+}
+
+/// Two-stage K-weighting pre-filter (high-shelf + RLB high-pass) from BS.1770.
+/// Duplicated rather than shared with Orquestador's `LoudnessMeterNode`:
+/// Compositor and Orquestador never depend on each other, only on Espectro.
+struct KWeightingFilter {
+    shelf: Biquad,
+    highpass: Biquad,
+}
+
+impl KWeightingFilter {
+    fn new(sample_rate: f32) -> Self {
+        let mut shelf = Biquad::new();
+        shelf.update_high_shelf(1681.0, 4.0, 0.7071, sample_rate);
+
+        let mut highpass = Biquad::new();
+        highpass.update_high_pass(38.0, 0.5, sample_rate);
+
+        Self { shelf, highpass }
+    }
+
+    fn process(&mut self, sample: f32) -> f32 {
+        self.highpass.process(self.shelf.process(sample))
+    }
+
+    fn reset(&mut self) {
+        self.shelf.reset();
+        self.highpass.reset();
+    }
+}
+
+/// Minimal single-channel biquad, used only for K-weighting here
+struct Biquad {
+    a0: f32,
+    a1: f32,
+    a2: f32,
+    b1: f32,
+    b2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Biquad {
+    fn new() -> Self {
+        Self { a0: 1.0, a1: 0.0, a2: 0.0, b1: 0.0, b2: 0.0, x1: 0.0, x2: 0.0, y1: 0.0, y2: 0.0 }
+    }
+
+    fn set_coeffs(&mut self, b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) {
+        self.a0 = b0 / a0;
+        self.a1 = b1 / a0;
+        self.a2 = b2 / a0;
+        self.b1 = a1 / a0;
+        self.b2 = a2 / a0;
+    }
+
+    fn update_high_shelf(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let alpha = w0.sin() / (2.0 * q);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let b0 = a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+        let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+        let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_high_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = (1.0 + cos_w0) / 2.0;
+        let b1 = -(1.0 + cos_w0);
+        let b2 = (1.0 + cos_w0) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.a0 * x + self.a1 * self.x1 + self.a2 * self.x2
+            - self.b1 * self.y1 - self.b2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+
+    fn reset(&mut self) {
+        self.x1 = 0.0;
+        self.x2 = 0.0;
+        self.y1 = 0.0;
+        self.y2 = 0.0;
+    }
+}
+
+/// Channel weight per BS.1770: channels 0 and 1 (L/R) get unity weight and every
+/// further channel gets the 1.41x surround weight; no layout is consulted, so
+/// center and LFE are weighted as surrounds too
+fn channel_weight(channel: usize, channels: usize) -> f32 {
+    if channels <= 2 || channel < 2 {
+        1.0
+    } else {
+        1.41
+    }
+}
+
+impl LoudnessNormNode {
+    /// Create a new loudness normalization node
+    pub fn new(id: String) -> Self {
+        Self::with_sample_rate(id, -24.0, 1.0, -2.0, 48000.0)
+    }
+
+    fn with_sample_rate(id: String, target_lufs: f32, range_lu: f32, max_true_peak_db: f32, sample_rate: f32) -> Self {
+        let hop_size = (sample_rate * 0.1) as usize;
+        Self {
+            id,
+            target_lufs,
+            range_lu,
+            max_true_peak_db,
+            sample_rate,
+            k_weight: Vec::new(),
+            hop_accumulator: 0.0,
+            hop_samples_seen: 0,
+            hop_size: hop_size.max(1),
+            recent_hops: VecDeque::with_capacity(BLOCK_HOPS),
+            gating_blocks: Vec::new(),
+            measured_integrated_lufs: f32::NEG_INFINITY,
+            gain: SmoothedParameter::new(1.0, DEFAULT_GAIN_SMOOTHING_MS),
+        }
+    }
+
+    fn ensure_channels(&mut self, channels: usize) {
+        if self.k_weight.len() != channels {
+            self.k_weight = (0..channels).map(|_| KWeightingFilter::new(self.sample_rate)).collect();
+        }
+    }
+
+    fn db_to_linear(db: f32) -> f32 {
+        10.0_f32.powf(db / 20.0)
+    }
+
+    fn finish_hop(&mut self) {
+        self.recent_hops.push_back(self.hop_accumulator);
+        while self.recent_hops.len() > BLOCK_HOPS {
+            self.recent_hops.pop_front();
+        }
+
+        if self.recent_hops.len() == BLOCK_HOPS {
+            let window_energy: f32 = self.recent_hops.iter().sum::<f32>()
+                / (BLOCK_HOPS * self.hop_size) as f32;
+            self.gating_blocks.push(window_energy);
+            self.measured_integrated_lufs = Self::gated_integrated_lufs(&self.gating_blocks);
+        }
+
+        self.hop_accumulator = 0.0;
+        self.hop_samples_seen = 0;
+
+        self.gain.set_target(Self::db_to_linear(self.correction_gain_db()));
+    }
+
+    /// Two-stage gated integrated loudness, per BS.1770 / EBU R128
+    fn gated_integrated_lufs(blocks: &[f32]) -> f32 {
+        if blocks.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let absolute_gate_energy = 10.0_f32.powf((ABSOLUTE_GATE_LUFS + 0.691) / 10.0);
+        let survivors: Vec<f32> = blocks.iter().copied().filter(|&e| e > absolute_gate_energy).collect();
+        if survivors.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let mean_energy: f32 = survivors.iter().sum::<f32>() / survivors.len() as f32;
+        let relative_gate_lufs = -0.691 + 10.0 * mean_energy.log10() + RELATIVE_GATE_OFFSET;
+        let relative_gate_energy = 10.0_f32.powf((relative_gate_lufs + 0.691) / 10.0);
+
+        let gated: Vec<f32> = survivors.into_iter().filter(|&e| e > relative_gate_energy).collect();
+        if gated.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let gated_mean = gated.iter().sum::<f32>() / gated.len() as f32;
+        -0.691 + 10.0 * gated_mean.log10()
+    }
+
+    /// Gain needed to reach `target_lufs`, or 0 dB while inside the `range` deadband
+    fn correction_gain_db(&self) -> f32 {
+        if self.measured_integrated_lufs.is_infinite() {
+            return 0.0;
+        }
+
+        let diff = self.target_lufs - self.measured_integrated_lufs;
+        if diff.abs() <= self.range_lu / 2.0 {
+            0.0
+        } else {
+            diff
+        }
+    }
+
+    /// Oversample a run of samples (linear interpolation) and return the peak absolute value
+    fn true_peak_of(window: &[f32; 2]) -> f32 {
+        let mut peak = window[0].abs().max(window[1].abs());
+        for step in 1..OVERSAMPLE_FACTOR {
+            let t = step as f32 / OVERSAMPLE_FACTOR as f32;
+            let interpolated = window[0] + (window[1] - window[0]) * t;
+            peak = peak.max(interpolated.abs());
+        }
+        peak
+    }
+
+    /// Brickwall-limit `buffer` so its oversampled true peak never exceeds `max_true_peak_db`
+    fn limit_true_peak(&self, buffer: &mut AudioBuffer) {
+        let frames = buffer.num_frames();
+        if frames == 0 {
+            return;
+        }
+
+        let mut peak = 0.0f32;
+        for ch in 0..buffer.channels {
+            for frame in 0..frames {
+                let next = (frame + 1).min(frames - 1);
+                let window = [
+                    buffer.samples[frame * buffer.channels + ch],
+                    buffer.samples[next * buffer.channels + ch],
+                ];
+                peak = peak.max(Self::true_peak_of(&window));
+            }
+        }
+
+        let ceiling_linear = Self::db_to_linear(self.max_true_peak_db);
+        if peak > ceiling_linear && peak > 0.0 {
+            let scale = ceiling_linear / peak;
+            for sample in buffer.samples.iter_mut() {
+                *sample *= scale;
+            }
+        }
+    }
+
+    /// Integrated loudness measured so far, in LUFS (`f32::NEG_INFINITY` until enough audio has passed the gate)
+    pub fn measured_loudness(&self) -> f32 {
+        self.measured_integrated_lufs
+    }
+}
+
+impl AudioNode for LoudnessNormNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> &str {
+        "LoudnessNormNode"
+    }
+
+    fn metadata(&self) -> NodeMetadata {
+        NodeMetadata {
+            name: "Loudness Normalizer".to_string(),
+            category: NodeCategory::Effect,
+            input_channels: 2,
+            output_channels: 2,
+            parameters: vec![
+                ParameterDescriptor::new("target", -40.0, 0.0, -24.0, "LUFS", "Target Loudness"),
+                ParameterDescriptor::new("range", 0.0, 20.0, 1.0, "LU", "Deadband"),
+                ParameterDescriptor::new("max_true_peak", -10.0, 0.0, -2.0, "dBTP", "Max True Peak"),
+            ],
+            plugin: "compositor".to_string(),
+        }
+    }
+
+    fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+        if (self.sample_rate - input.sample_rate as f32).abs() > f32::EPSILON {
+            let (target, range, ceiling) = (self.target_lufs, self.range_lu, self.max_true_peak_db);
+            *self = Self::with_sample_rate(self.id.clone(), target, range, ceiling, input.sample_rate as f32);
+        }
+        self.ensure_channels(input.channels);
+
+        let frames = input.num_frames();
+        for frame in 0..frames {
+            let base = frame * input.channels;
+            let mut weighted_sum_sq = 0.0;
+            for ch in 0..input.channels {
+                let weighted = self.k_weight[ch].process(input.samples[base + ch]);
+                weighted_sum_sq += weighted * weighted * channel_weight(ch, input.channels);
+            }
+
+            self.hop_accumulator += weighted_sum_sq;
+            self.hop_samples_seen += 1;
+            if self.hop_samples_seen >= self.hop_size {
+                self.finish_hop();
+            }
+        }
+
+        let mut output = input.clone();
+        self.gain.prepare(input.sample_rate);
+        for frame in output.samples.chunks_mut(output.channels) {
+            let gain = self.gain.next();
+            for sample in frame.iter_mut() {
+                *sample *= gain;
+            }
+        }
+
+        self.limit_true_peak(&mut output);
+
+        Ok(output)
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+        match name {
+            "target" => self.target_lufs = value.clamp(-40.0, 0.0),
+            "range" => self.range_lu = value.clamp(0.0, 20.0),
+            "max_true_peak" => self.max_true_peak_db = value.clamp(-10.0, 0.0),
+            _ => return Err(GraphError::ParameterNotFound(name.to_string())),
+        }
+        Ok(())
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        match name {
+            "target" => Some(self.target_lufs),
+            "range" => Some(self.range_lu),
+            "max_true_peak" => Some(self.max_true_peak_db),
+            "measured_lufs" => Some(self.measured_integrated_lufs),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        for filter in &mut self.k_weight {
+            filter.reset();
+        }
+        self.hop_accumulator = 0.0;
+        self.hop_samples_seen = 0;
+        self.recent_hops.clear();
+        self.gating_blocks.clear();
+        self.measured_integrated_lufs = f32::NEG_INFINITY;
+        self.gain.set_immediate(1.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_norm_creation_defaults() {
+        let node = LoudnessNormNode::new("norm1".to_string());
+        assert_eq!(node.get_parameter("target"), Some(-24.0));
+        assert_eq!(node.get_parameter("max_true_peak"), Some(-2.0));
+        assert_eq!(node.measured_loudness(), f32::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_norm_passthrough_until_measured() {
+        let mut node = LoudnessNormNode::new("norm1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 256);
+        input.samples.iter_mut().enumerate().for_each(|(i, s)| *s = 0.1 * (i as f32 * 0.01).sin());
+
+        let output = node.process(&input).unwrap();
+        assert_eq!(output.samples, input.samples);
+    }
+
+    #[test]
+    fn test_norm_limits_true_peak() {
+        let mut node = LoudnessNormNode::new("norm1".to_string());
+        node.set_parameter("max_true_peak", -6.0).unwrap();
+
+        let mut input = AudioBuffer::new(2, 48000, 4);
+        input.samples = vec![0.99, 0.99, 0.1, 0.1, -0.1, -0.1, 0.05, 0.05];
+
+        let output = node.process(&input).unwrap();
+        let ceiling = 10.0_f32.powf(-6.0 / 20.0);
+        assert!(output.samples.iter().all(|s| s.abs() <= ceiling + 1e-4));
+    }
+
+    #[test]
+    fn test_norm_reset_clears_measurement() {
+        let mut node = LoudnessNormNode::new("norm1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 48000);
+        input.samples.iter_mut().enumerate().for_each(|(i, s)| *s = 0.3 * (i as f32 * 0.02).sin());
+        node.process(&input).unwrap();
+
+        node.reset();
+        assert_eq!(node.measured_loudness(), f32::NEG_INFINITY);
+    }
+}
@@ -1,7 +1,11 @@
 pub mod eq;
 pub mod gain;
 pub mod compressor;
+pub mod loudness_norm;
+pub mod denoise;
 
 pub use eq::EqualizerNode;
 pub use gain::GainNode;
 pub use compressor::CompressorNode;
+pub use loudness_norm::LoudnessNormNode;
+pub use denoise::DenoiseNode;
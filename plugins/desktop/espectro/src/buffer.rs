@@ -1,4 +1,10 @@
 use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// PCM integer format tag, per the WAVE `fmt ` chunk
+const WAVE_FORMAT_PCM: u16 = 1;
+/// IEEE float format tag, per the WAVE `fmt ` chunk
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
 
 /// Audio buffer containing interleaved samples
 /// 
@@ -45,13 +51,62 @@ impl AudioBuffer {
         self.samples.fill(0.0);
     }
     
-    /// Mix another buffer into this one
+    /// Mix another buffer into this one, resampling it first if its rate
+    /// doesn't match - otherwise the two streams would be summed sample-for-
+    /// sample at different underlying playback speeds.
     pub fn mix(&mut self, other: &AudioBuffer) {
-        let len = self.samples.len().min(other.samples.len());
+        let resampled;
+        let source = if other.sample_rate != self.sample_rate {
+            resampled = other.resample(self.sample_rate);
+            &resampled
+        } else {
+            other
+        };
+
+        let len = self.samples.len().min(source.samples.len());
         for i in 0..len {
-            self.samples[i] += other.samples[i];
+            self.samples[i] += source.samples[i];
         }
     }
+
+    /// Resample to `target_rate` via linear interpolation between
+    /// neighboring frames. Tracks position as an integer frame index plus a
+    /// fractional accumulator (out of `target_rate`): each output step
+    /// advances the accumulator by `self.sample_rate` and carries into the
+    /// index once it reaches `target_rate`.
+    pub fn resample(&self, target_rate: u32) -> AudioBuffer {
+        if self.sample_rate == target_rate || self.channels == 0 {
+            return self.clone();
+        }
+
+        let in_frames = self.num_frames();
+        if in_frames == 0 {
+            return AudioBuffer::new(self.channels, target_rate, 0);
+        }
+
+        let out_frames = ((in_frames as u64 * target_rate as u64) as f64 / self.sample_rate as f64)
+            .ceil() as usize;
+        let mut output = AudioBuffer::new(self.channels, target_rate, out_frames);
+
+        let mut index = 0usize;
+        let mut frac = 0u32;
+        for out_frame in 0..out_frames {
+            let next_index = (index + 1).min(in_frames - 1);
+            let t = frac as f32 / target_rate as f32;
+
+            for ch in 0..self.channels {
+                let a = self.samples[index * self.channels + ch];
+                let b = self.samples[next_index * self.channels + ch];
+                output.samples[out_frame * self.channels + ch] = a + (b - a) * t;
+            }
+
+            frac += self.sample_rate;
+            index = (index + (frac / target_rate) as usize).min(in_frames - 1);
+            frac %= target_rate;
+        }
+
+        output
+    }
     
     /// Apply gain to all samples
     pub fn apply_gain(&mut self, gain: f32) {
@@ -82,6 +137,193 @@ impl AudioBuffer {
     pub fn resize(&mut self, num_samples: usize) {
         self.samples.resize(num_samples * self.channels, 0.0);
     }
+
+    /// Convert to planar (deinterleaved) form: one contiguous `Vec<f32>` per
+    /// channel. Filters that must run independently per channel (K-weighting,
+    /// the resampler, the denoiser) can operate on a plane directly instead
+    /// of manually striding by `channels`.
+    pub fn to_planar(&self) -> Vec<Vec<f32>> {
+        let frames = self.num_frames();
+        let mut planes = vec![Vec::with_capacity(frames); self.channels];
+        for frame in 0..frames {
+            for (ch, plane) in planes.iter_mut().enumerate() {
+                plane.push(self.samples[frame * self.channels + ch]);
+            }
+        }
+        planes
+    }
+
+    /// Build an interleaved buffer from planar (deinterleaved) channel data.
+    /// Planes shorter than the longest one contribute silence for their
+    /// missing trailing frames.
+    pub fn from_planar(planes: &[Vec<f32>], sample_rate: u32) -> AudioBuffer {
+        let channels = planes.len();
+        let frames = planes.iter().map(|p| p.len()).max().unwrap_or(0);
+        let mut buffer = AudioBuffer::new(channels, sample_rate, frames);
+
+        for (ch, plane) in planes.iter().enumerate() {
+            for (frame, &sample) in plane.iter().enumerate() {
+                buffer.samples[frame * channels + ch] = sample;
+            }
+        }
+
+        buffer
+    }
+
+    /// Iterate one channel's samples without copying the whole buffer
+    pub fn channel(&self, ch: usize) -> impl Iterator<Item = f32> + '_ {
+        let channels = self.channels.max(1);
+        self.samples.get(ch..).into_iter().flat_map(move |s| s.iter().step_by(channels)).copied()
+    }
+
+    /// Mutably iterate one channel's samples in place
+    pub fn channel_mut(&mut self, ch: usize) -> impl Iterator<Item = &mut f32> {
+        let channels = self.channels.max(1);
+        self.samples.get_mut(ch..).into_iter().flat_map(move |s| s.iter_mut().step_by(channels))
+    }
+
+    /// Apply gain to a single channel only
+    pub fn apply_gain_channel(&mut self, ch: usize, gain: f32) {
+        for sample in self.channel_mut(ch) {
+            *sample *= gain;
+        }
+    }
+
+    /// Peak level (maximum absolute value) of a single channel
+    pub fn peak_level_channel(&self, ch: usize) -> f32 {
+        self.channel(ch).map(|s| s.abs()).fold(0.0f32, f32::max)
+    }
+
+    /// RMS level of a single channel
+    pub fn rms_level_channel(&self, ch: usize) -> f32 {
+        let mut sum = 0.0f32;
+        let mut count = 0usize;
+        for sample in self.channel(ch) {
+            sum += sample * sample;
+            count += 1;
+        }
+        if count == 0 {
+            0.0
+        } else {
+            (sum / count as f32).sqrt()
+        }
+    }
+
+    /// Load a RIFF/WAVE file, independent of the lofty-based metadata path.
+    /// Supports PCM 16/24/32-bit integer and IEEE float data, normalizing
+    /// every sample to `-1.0..1.0`. Unknown chunks before `fmt `/`data` (e.g.
+    /// `JUNK`) are skipped.
+    pub fn from_wav(path: &str) -> Result<AudioBuffer, String> {
+        let bytes = fs::read(path).map_err(|e| format!("Failed to read WAV file: {}", e))?;
+        Self::parse_wav(&bytes)
+    }
+
+    fn parse_wav(bytes: &[u8]) -> Result<AudioBuffer, String> {
+        if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+            return Err("Not a RIFF/WAVE file".to_string());
+        }
+
+        let mut channels = 0usize;
+        let mut sample_rate = 0u32;
+        let mut bits_per_sample = 0u16;
+        let mut format_tag = 0u16;
+        let mut data: Option<&[u8]> = None;
+
+        let mut offset = 12;
+        while offset + 8 <= bytes.len() {
+            let chunk_id = &bytes[offset..offset + 4];
+            let chunk_size = u32::from_le_bytes(
+                bytes[offset + 4..offset + 8].try_into().map_err(|_| "Malformed WAV chunk header")?,
+            ) as usize;
+            let body_start = offset + 8;
+
+            if chunk_id == b"fmt " {
+                if body_start + 16 > bytes.len() {
+                    return Err("Truncated WAV fmt chunk".to_string());
+                }
+                format_tag = u16::from_le_bytes(bytes[body_start..body_start + 2].try_into().unwrap());
+                channels = u16::from_le_bytes(bytes[body_start + 2..body_start + 4].try_into().unwrap()) as usize;
+                sample_rate = u32::from_le_bytes(bytes[body_start + 4..body_start + 8].try_into().unwrap());
+                bits_per_sample = u16::from_le_bytes(bytes[body_start + 14..body_start + 16].try_into().unwrap());
+            } else if chunk_id == b"data" {
+                if body_start + chunk_size > bytes.len() {
+                    return Err("WAV data chunk size exceeds file length".to_string());
+                }
+                data = Some(&bytes[body_start..body_start + chunk_size]);
+            }
+
+            // Chunks are word-aligned: an odd-sized chunk has a padding byte
+            offset = body_start + chunk_size + (chunk_size % 2);
+        }
+
+        if channels == 0 {
+            return Err("WAV file is missing a fmt chunk".to_string());
+        }
+        let data = data.ok_or("WAV file is missing a data chunk")?;
+
+        let samples = Self::decode_pcm(data, format_tag, bits_per_sample)?;
+
+        Ok(AudioBuffer { channels, sample_rate, samples })
+    }
+
+    /// Convert raw little-endian `data` chunk bytes to normalized `f32` samples
+    fn decode_pcm(data: &[u8], format_tag: u16, bits_per_sample: u16) -> Result<Vec<f32>, String> {
+        match (format_tag, bits_per_sample) {
+            (WAVE_FORMAT_PCM, 16) => Ok(data
+                .chunks_exact(2)
+                .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0)
+                .collect()),
+            (WAVE_FORMAT_PCM, 24) => Ok(data
+                .chunks_exact(3)
+                .map(|b| {
+                    let mut v = ((b[2] as i32) << 16) | ((b[1] as i32) << 8) | (b[0] as i32);
+                    if v & 0x0080_0000 != 0 {
+                        v -= 0x0100_0000; // sign-extend the 24-bit two's complement value
+                    }
+                    v as f32 / 8_388_608.0
+                })
+                .collect()),
+            (WAVE_FORMAT_PCM, 32) => Ok(data
+                .chunks_exact(4)
+                .map(|b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2_147_483_648.0)
+                .collect()),
+            (WAVE_FORMAT_IEEE_FLOAT, 32) => Ok(data
+                .chunks_exact(4)
+                .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()),
+            _ => Err(format!(
+                "Unsupported WAV format: tag={} bits_per_sample={}",
+                format_tag, bits_per_sample
+            )),
+        }
+    }
+
+    /// Write a canonical 32-bit float RIFF/WAVE file
+    pub fn write_wav(&self, path: &str) -> Result<(), String> {
+        let data_bytes = self.samples.len() * 4;
+        let byte_rate = self.sample_rate * self.channels as u32 * 4;
+        let block_align = (self.channels * 4) as u16;
+
+        let mut buf = Vec::with_capacity(44 + data_bytes);
+        buf.extend_from_slice(b"RIFF");
+        buf.extend_from_slice(&((36 + data_bytes) as u32).to_le_bytes());
+        buf.extend_from_slice(b"WAVE");
+        buf.extend_from_slice(b"fmt ");
+        buf.extend_from_slice(&16u32.to_le_bytes());
+        buf.extend_from_slice(&WAVE_FORMAT_IEEE_FLOAT.to_le_bytes());
+        buf.extend_from_slice(&(self.channels as u16).to_le_bytes());
+        buf.extend_from_slice(&self.sample_rate.to_le_bytes());
+        buf.extend_from_slice(&byte_rate.to_le_bytes());
+        buf.extend_from_slice(&block_align.to_le_bytes());
+        buf.extend_from_slice(&32u16.to_le_bytes());
+        buf.extend_from_slice(b"data");
+        buf.extend_from_slice(&(data_bytes as u32).to_le_bytes());
+        for sample in &self.samples {
+            buf.extend_from_slice(&sample.to_le_bytes());
+        }
+
+        fs::write(path, &buf).map_err(|e| format!("Failed to write WAV file: {}", e))
+    }
 }
 
 #[cfg(test)]
@@ -133,7 +375,188 @@ mod tests {
     fn test_peak_level() {
         let mut buffer = AudioBuffer::new(1, 48000, 4);
         buffer.samples = vec![0.5, -0.8, 0.3, -0.2];
-        
+
         assert!((buffer.peak_level() - 0.8).abs() < 0.001);
     }
+
+    #[test]
+    fn test_resample_same_rate_is_noop() {
+        let buffer = AudioBuffer::new(2, 48000, 16);
+        let resampled = buffer.resample(48000);
+        assert_eq!(resampled.samples, buffer.samples);
+    }
+
+    #[test]
+    fn test_resample_changes_frame_count() {
+        let buffer = AudioBuffer::new(2, 44100, 441);
+        let resampled = buffer.resample(48000);
+
+        assert_eq!(resampled.sample_rate, 48000);
+        assert_eq!(resampled.num_frames(), ((441u64 * 48000) as f64 / 44100.0).ceil() as usize);
+    }
+
+    #[test]
+    fn test_resample_interpolates_linearly() {
+        let mut buffer = AudioBuffer::new(1, 2, 2);
+        buffer.samples = vec![0.0, 1.0];
+
+        let resampled = buffer.resample(4);
+        assert_eq!(resampled.num_frames(), 4);
+        assert!((resampled.samples[0] - 0.0).abs() < 0.001);
+        assert!((resampled.samples[1] - 0.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_wav_roundtrip() {
+        let mut buffer = AudioBuffer::new(2, 44100, 4);
+        buffer.samples = vec![0.5, -0.5, 0.25, -0.25, 1.0, -1.0, 0.0, 0.0];
+
+        let path = std::env::temp_dir().join("espectro_test_wav_roundtrip.wav");
+        buffer.write_wav(path.to_str().unwrap()).unwrap();
+
+        let loaded = AudioBuffer::from_wav(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded.channels, 2);
+        assert_eq!(loaded.sample_rate, 44100);
+        for (a, b) in loaded.samples.iter().zip(buffer.samples.iter()) {
+            assert!((a - b).abs() < 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_wav_decodes_16bit_pcm() {
+        let mut wav = Vec::new();
+        wav.extend_from_slice(b"RIFF");
+        wav.extend_from_slice(&36u32.to_le_bytes());
+        wav.extend_from_slice(b"WAVE");
+        wav.extend_from_slice(b"fmt ");
+        wav.extend_from_slice(&16u32.to_le_bytes());
+        wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+        wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+        wav.extend_from_slice(&48000u32.to_le_bytes());
+        wav.extend_from_slice(&96000u32.to_le_bytes());
+        wav.extend_from_slice(&2u16.to_le_bytes());
+        wav.extend_from_slice(&16u16.to_le_bytes());
+        wav.extend_from_slice(b"data");
+        wav.extend_from_slice(&4u32.to_le_bytes());
+        wav.extend_from_slice(&i16::MAX.to_le_bytes());
+        wav.extend_from_slice(&i16::MIN.to_le_bytes());
+
+        let path = std::env::temp_dir().join("espectro_test_wav_16bit.wav");
+        std::fs::write(&path, &wav).unwrap();
+
+        let buffer = AudioBuffer::from_wav(path.to_str().unwrap()).unwrap();
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(buffer.channels, 1);
+        assert_eq!(buffer.sample_rate, 48000);
+        assert!((buffer.samples[0] - 1.0).abs() < 0.001);
+        assert!((buffer.samples[1] - (-1.0)).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_wav_rejects_non_riff_data() {
+        let path = std::env::temp_dir().join("espectro_test_wav_bogus.wav");
+        std::fs::write(&path, b"not a wav file").unwrap();
+        let result = AudioBuffer::from_wav(path.to_str().unwrap());
+        let _ = std::fs::remove_file(&path);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_mix_resamples_mismatched_rate() {
+        let mut buf1 = AudioBuffer::new(1, 48000, 4);
+        buf1.samples = vec![0.5, 0.5, 0.5, 0.5];
+
+        let mut buf2 = AudioBuffer::new(1, 24000, 2);
+        buf2.samples = vec![0.3, 0.3];
+
+        buf1.mix(&buf2);
+
+        assert!(buf1.samples.iter().all(|s| s.is_finite()));
+        assert!((buf1.samples[0] - 0.8).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_to_planar_deinterleaves() {
+        let mut buffer = AudioBuffer::new(2, 48000, 3);
+        buffer.samples = vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0];
+
+        let planes = buffer.to_planar();
+        assert_eq!(planes.len(), 2);
+        assert_eq!(planes[0], vec![1.0, 2.0, 3.0]);
+        assert_eq!(planes[1], vec![-1.0, -2.0, -3.0]);
+    }
+
+    #[test]
+    fn test_from_planar_interleaves() {
+        let planes = vec![vec![1.0, 2.0, 3.0], vec![-1.0, -2.0, -3.0]];
+        let buffer = AudioBuffer::from_planar(&planes, 48000);
+
+        assert_eq!(buffer.channels, 2);
+        assert_eq!(buffer.samples, vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0]);
+    }
+
+    #[test]
+    fn test_planar_roundtrip() {
+        let mut buffer = AudioBuffer::new(3, 48000, 4);
+        buffer.samples = (0..12).map(|i| i as f32).collect();
+
+        let roundtripped = AudioBuffer::from_planar(&buffer.to_planar(), buffer.sample_rate);
+        assert_eq!(roundtripped.samples, buffer.samples);
+        assert_eq!(roundtripped.channels, buffer.channels);
+    }
+
+    #[test]
+    fn test_channel_iterates_single_channel() {
+        let mut buffer = AudioBuffer::new(2, 48000, 3);
+        buffer.samples = vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0];
+
+        assert_eq!(buffer.channel(0).collect::<Vec<_>>(), vec![1.0, 2.0, 3.0]);
+        assert_eq!(buffer.channel(1).collect::<Vec<_>>(), vec![-1.0, -2.0, -3.0]);
+    }
+
+    #[test]
+    fn test_channel_mut_modifies_in_place() {
+        let mut buffer = AudioBuffer::new(2, 48000, 3);
+        buffer.samples = vec![1.0, -1.0, 2.0, -2.0, 3.0, -3.0];
+
+        for sample in buffer.channel_mut(0) {
+            *sample *= 10.0;
+        }
+
+        assert_eq!(buffer.samples, vec![10.0, -1.0, 20.0, -2.0, 30.0, -3.0]);
+    }
+
+    #[test]
+    fn test_apply_gain_channel_affects_only_that_channel() {
+        let mut buffer = AudioBuffer::new(2, 48000, 2);
+        buffer.samples = vec![1.0, 1.0, 1.0, 1.0];
+
+        buffer.apply_gain_channel(0, 0.5);
+
+        assert!((buffer.samples[0] - 0.5).abs() < 0.001);
+        assert!((buffer.samples[1] - 1.0).abs() < 0.001);
+        assert!((buffer.samples[2] - 0.5).abs() < 0.001);
+        assert!((buffer.samples[3] - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_peak_level_channel() {
+        let mut buffer = AudioBuffer::new(2, 48000, 2);
+        buffer.samples = vec![0.2, 0.9, -0.4, -0.1];
+
+        assert!((buffer.peak_level_channel(0) - 0.4).abs() < 0.001);
+        assert!((buffer.peak_level_channel(1) - 0.9).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_rms_level_channel() {
+        let mut buffer = AudioBuffer::new(1, 48000, 4);
+        buffer.samples = vec![1.0, -1.0, 1.0, -1.0];
+
+        assert!((buffer.rms_level_channel(0) - 1.0).abs() < 0.001);
+    }
 }
@@ -0,0 +1,183 @@
+use std::f32::consts::FRAC_PI_4;
+
+/// The role a single channel plays within a `ChannelLayout`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelRole {
+    /// Single center channel of a `ChannelLayout::Mono` signal
+    Mono,
+    FrontLeft,
+    FrontRight,
+    FrontCenter,
+    /// Low-frequency effects ("subwoofer") channel
+    Lfe,
+    SurroundLeft,
+    SurroundRight,
+    BackLeft,
+    BackRight,
+}
+
+/// A named multichannel layout, with a fixed per-channel role ordering
+/// matching how samples are interleaved in `AudioBuffer::samples`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelLayout {
+    Mono,
+    Stereo,
+    /// Quadraphonic: front left/right, back left/right
+    Quad,
+    /// 5.1 surround: front left/right/center, LFE, surround left/right
+    Surround51,
+    /// 7.1 surround: 5.1 plus back left/right
+    Surround71,
+}
+
+impl ChannelLayout {
+    /// Map an `AudioBuffer::channels` count to its conventional layout.
+    ///
+    /// Returns `None` for channel counts with no single conventional layout
+    /// (e.g. 3), in which case callers should fall back to treating the
+    /// buffer as an unstructured set of channels.
+    pub fn from_channel_count(channels: usize) -> Option<Self> {
+        match channels {
+            1 => Some(Self::Mono),
+            2 => Some(Self::Stereo),
+            4 => Some(Self::Quad),
+            6 => Some(Self::Surround51),
+            8 => Some(Self::Surround71),
+            _ => None,
+        }
+    }
+
+    /// Number of channels in this layout
+    pub fn channel_count(&self) -> usize {
+        self.roles().len()
+    }
+
+    /// Per-channel roles, in interleaving order
+    pub fn roles(&self) -> &'static [ChannelRole] {
+        use ChannelRole::*;
+        match self {
+            Self::Mono => &[Mono],
+            Self::Stereo => &[FrontLeft, FrontRight],
+            Self::Quad => &[FrontLeft, FrontRight, BackLeft, BackRight],
+            Self::Surround51 => &[FrontLeft, FrontRight, FrontCenter, Lfe, SurroundLeft, SurroundRight],
+            Self::Surround71 => &[
+                FrontLeft, FrontRight, FrontCenter, Lfe, SurroundLeft, SurroundRight, BackLeft, BackRight,
+            ],
+        }
+    }
+
+    /// Per-channel equal-power pan gains for `pan` (-1.0 left, 0.0 center, 1.0 right).
+    ///
+    /// Only the front left/right pair (or, for `Mono`, the single channel)
+    /// is affected by panning; every other role (center, LFE, surrounds,
+    /// backs) is left untouched at unity gain, since those channels don't
+    /// have a left/right counterpart to pan between.
+    pub fn pan_gains(&self, pan: f32) -> Vec<f32> {
+        let pan_angle = (pan.clamp(-1.0, 1.0) + 1.0) * FRAC_PI_4; // 0..=PI/2
+        let left_gain = pan_angle.cos();
+        let right_gain = pan_angle.sin();
+
+        if *self == Self::Mono {
+            // No left/right pair to pan between; apply the same equal-power
+            // curve a hard pan would cost a stereo channel, so panning a
+            // mono source still attenuates it smoothly off-center.
+            return vec![(left_gain + right_gain) * std::f32::consts::FRAC_1_SQRT_2];
+        }
+
+        self.roles()
+            .iter()
+            .map(|role| match role {
+                ChannelRole::FrontLeft => left_gain,
+                ChannelRole::FrontRight => right_gain,
+                _ => 1.0,
+            })
+            .collect()
+    }
+
+    /// Downmix one frame of this layout's channels to stereo.
+    ///
+    /// Coefficients follow the ITU-R BS.775 downmix convention: center and
+    /// surround/back channels are folded in at -3 dB (0.707), LFE is dropped
+    /// entirely.
+    pub fn downmix_to_stereo(&self, frame: &[f32]) -> (f32, f32) {
+        const MIX: f32 = std::f32::consts::FRAC_1_SQRT_2;
+
+        match self {
+            Self::Mono => (frame[0], frame[0]),
+            Self::Stereo => (frame[0], frame[1]),
+            Self::Quad => {
+                let (fl, fr, bl, br) = (frame[0], frame[1], frame[2], frame[3]);
+                (fl + MIX * bl, fr + MIX * br)
+            }
+            Self::Surround51 => {
+                let (fl, fr, fc, _lfe, sl, sr) = (frame[0], frame[1], frame[2], frame[3], frame[4], frame[5]);
+                (fl + MIX * fc + MIX * sl, fr + MIX * fc + MIX * sr)
+            }
+            Self::Surround71 => {
+                let (fl, fr, fc, _lfe, sl, sr, bl, br) = (
+                    frame[0], frame[1], frame[2], frame[3], frame[4], frame[5], frame[6], frame[7],
+                );
+                (
+                    fl + MIX * fc + MIX * sl + MIX * bl,
+                    fr + MIX * fc + MIX * sr + MIX * br,
+                )
+            }
+        }
+    }
+
+    /// Upmix one stereo frame into this layout's channels.
+    ///
+    /// The front pair carries the stereo signal directly; center, LFE and
+    /// any rear/surround channels are left silent since they have no
+    /// corresponding source signal to derive from a plain stereo input.
+    pub fn upmix_from_stereo(&self, left: f32, right: f32) -> Vec<f32> {
+        match self {
+            Self::Mono => vec![(left + right) * 0.5],
+            Self::Stereo => vec![left, right],
+            Self::Quad => vec![left, right, 0.0, 0.0],
+            Self::Surround51 => vec![left, right, 0.0, 0.0, 0.0, 0.0],
+            Self::Surround71 => vec![left, right, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_channel_count() {
+        assert_eq!(ChannelLayout::from_channel_count(1), Some(ChannelLayout::Mono));
+        assert_eq!(ChannelLayout::from_channel_count(2), Some(ChannelLayout::Stereo));
+        assert_eq!(ChannelLayout::from_channel_count(6), Some(ChannelLayout::Surround51));
+        assert_eq!(ChannelLayout::from_channel_count(3), None);
+    }
+
+    #[test]
+    fn test_stereo_pan_gains_center() {
+        let gains = ChannelLayout::Stereo.pan_gains(0.0);
+        assert!((gains[0] - gains[1]).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_surround_pan_leaves_lfe_untouched() {
+        let gains = ChannelLayout::Surround51.pan_gains(1.0);
+        // [FL, FR, FC, LFE, SL, SR]
+        assert_eq!(gains[3], 1.0);
+        assert_eq!(gains[2], 1.0);
+    }
+
+    #[test]
+    fn test_downmix_quad_to_stereo() {
+        let (l, r) = ChannelLayout::Quad.downmix_to_stereo(&[1.0, 1.0, 1.0, 1.0]);
+        assert!(l > 1.0 && r > 1.0);
+    }
+
+    #[test]
+    fn test_upmix_stereo_to_surround() {
+        let frame = ChannelLayout::Surround51.upmix_from_stereo(0.5, 0.8);
+        assert_eq!(frame[0], 0.5);
+        assert_eq!(frame[1], 0.8);
+        assert_eq!(frame[3], 0.0); // LFE silent
+    }
+}
@@ -16,6 +16,9 @@ pub enum GraphError {
     
     #[error("Parameter '{0}' not found")]
     ParameterNotFound(String),
+
+    #[error("Unknown node type: '{0}'")]
+    UnknownNodeType(String),
     
     #[error("Invalid parameter value: {0}")]
     InvalidParameterValue(String),
@@ -28,6 +31,9 @@ pub enum GraphError {
     
     #[error("Channel count mismatch: expected {expected}, got {actual}")]
     ChannelMismatch { expected: usize, actual: usize },
+
+    #[error("Sample rate mismatch: expected {expected} Hz, got {actual} Hz (insert a ResamplerNode to reconcile)")]
+    SampleRateMismatch { expected: u32, actual: u32 },
 }
 
 pub type Result<T> = std::result::Result<T, GraphError>;
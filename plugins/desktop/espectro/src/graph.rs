@@ -1,4 +1,4 @@
-use crate::{AudioBuffer, AudioNode, Connection, GraphError, Result};
+use crate::{AudioBuffer, AudioNode, Connection, GraphError, NodeMetadata, Result};
 use std::collections::{HashMap, HashSet, VecDeque};
 use parking_lot::RwLock;
 use std::sync::Arc;
@@ -11,28 +11,45 @@ use std::sync::Arc;
 pub struct AudioGraph {
     /// All nodes in the graph
     nodes: HashMap<String, Box<dyn AudioNode>>,
-    
+
     /// All connections between nodes
     connections: Vec<Connection>,
-    
+
     /// Topologically sorted execution order
     execution_order: Vec<String>,
-    
+
     /// Cached buffers for intermediate results
     buffer_cache: HashMap<String, AudioBuffer>,
+
+    /// The sample rate this graph processes at. `process` rejects input
+    /// buffers at any other rate (see `GraphError::SampleRateMismatch`) —
+    /// reconcile a mismatched source or device by feeding it through a
+    /// `ResamplerNode` targeting this rate first.
+    sample_rate: u32,
 }
 
 impl AudioGraph {
-    /// Create a new empty audio graph
+    /// Create a new empty audio graph running at 48 kHz
     pub fn new() -> Self {
+        Self::with_sample_rate(48000)
+    }
+
+    /// Create a new empty audio graph running at a specific sample rate
+    pub fn with_sample_rate(sample_rate: u32) -> Self {
         Self {
             nodes: HashMap::new(),
             connections: Vec::new(),
             execution_order: Vec::new(),
             buffer_cache: HashMap::new(),
+            sample_rate,
         }
     }
-    
+
+    /// The sample rate this graph processes at
+    pub fn sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
     /// Add a node to the graph
     /// 
     /// # Arguments
@@ -74,10 +91,15 @@ impl AudioGraph {
     }
     
     /// Connect two nodes
-    /// 
+    ///
+    /// Nodes don't declare a fixed sample rate up front (it only exists on
+    /// the `AudioBuffer`s flowing through them at process time), so rate
+    /// compatibility can't be checked here — it's enforced in `process` and
+    /// `gather_inputs` instead, once real buffers are available.
+    ///
     /// # Arguments
     /// * `connection` - The connection to create
-    /// 
+    ///
     /// # Errors
     /// * `GraphError::NodeNotFound` if either node doesn't exist
     /// * `GraphError::CycleDetected` if the connection would create a cycle
@@ -122,34 +144,99 @@ impl AudioGraph {
     /// 
     /// # Returns
     /// Final output buffer (from sink nodes)
+    ///
+    /// # Errors
+    /// Returns `GraphError::SampleRateMismatch` if `input` isn't at the
+    /// graph's configured `sample_rate` — route it through a `ResamplerNode`
+    /// first rather than feeding mismatched-rate audio into the graph.
     pub fn process(&mut self, input: AudioBuffer) -> Result<AudioBuffer> {
+        if input.sample_rate != self.sample_rate {
+            return Err(GraphError::SampleRateMismatch {
+                expected: self.sample_rate,
+                actual: input.sample_rate,
+            });
+        }
+
         self.buffer_cache.clear();
-        
+
         // Store input for source nodes
         if let Some(first_node_id) = self.execution_order.first() {
             self.buffer_cache.insert(first_node_id.clone(), input);
         }
-        
+
+        let solo_active = self.solo_active();
+
         // Process nodes in topological order
         for node_id in &self.execution_order.clone() {
             // Gather inputs BEFORE getting mutable reference to node
             let node_input = self.gather_inputs(node_id)?;
-            
+
+            // If some other strip on this node's downstream bus is soloed
+            // (and this one isn't), force it muted for this block only,
+            // restoring the user's actual mute setting right after.
+            let solo_override = solo_active && self.needs_solo_mute(node_id);
+
             // Now get mutable reference and process
             let node = self.nodes.get_mut(node_id)
                 .ok_or_else(|| GraphError::NodeNotFound(node_id.clone()))?;
-            
+
+            let original_mute = if solo_override { node.get_parameter("mute") } else { None };
+            if solo_override {
+                let _ = node.set_parameter("mute", 1.0);
+            }
+
             let output = node.process(&node_input)
                 .map_err(|e| GraphError::ProcessingError(e.to_string()))?;
-            
+
+            if let Some(mute) = original_mute {
+                let _ = node.set_parameter("mute", mute);
+            }
+
             // Store output for downstream nodes
             self.buffer_cache.insert(node_id.clone(), output);
         }
-        
+
         // Return final output (from sink nodes)
         self.get_final_output()
     }
-    
+
+    /// Whether any node in the graph currently reports `solo == 1`
+    pub fn solo_active(&self) -> bool {
+        self.nodes.values().any(|n| n.get_parameter("solo") == Some(1.0))
+    }
+
+    /// The "bus" a node feeds: the node ID it's directly connected to (e.g.
+    /// a downstream `MixerNode`). Strips sharing a bus are the siblings
+    /// solo arbitration considers.
+    fn solo_bus(&self, node_id: &str) -> Option<String> {
+        self.connections.iter()
+            .find(|c| c.from_node == node_id)
+            .map(|c| c.to_node.clone())
+    }
+
+    /// Whether `node_id` should be forced silent this block: it isn't
+    /// itself soloed, but a sibling feeding the same bus is.
+    fn needs_solo_mute(&self, node_id: &str) -> bool {
+        let node = match self.nodes.get(node_id) {
+            Some(n) => n,
+            None => return false,
+        };
+
+        // Nodes without a "solo" parameter (e.g. effects, the mixer itself)
+        // are never subject to solo arbitration.
+        if node.get_parameter("solo").is_none() {
+            return false;
+        }
+        if node.get_parameter("solo") == Some(1.0) {
+            return false;
+        }
+
+        let bus = self.solo_bus(node_id);
+        self.nodes.iter().any(|(id, other)| {
+            id != node_id && self.solo_bus(id) == bus && other.get_parameter("solo") == Some(1.0)
+        })
+    }
+
     /// Set a parameter on a node
     /// 
     /// # Arguments
@@ -159,11 +246,29 @@ impl AudioGraph {
     pub fn set_parameter(&mut self, node_id: &str, parameter: &str, value: f32) -> Result<()> {
         let node = self.nodes.get_mut(node_id)
             .ok_or_else(|| GraphError::NodeNotFound(node_id.to_string()))?;
-        
+
         node.set_parameter(parameter, value)
             .map_err(|e| GraphError::ProcessingError(e.to_string()))
     }
-    
+
+    /// Like `set_parameter`, but ramps to the new value over `ramp_ms`
+    /// milliseconds instead of snapping instantly (see
+    /// `AudioNode::set_parameter_smoothed`), so automating a node's
+    /// parameter from the UI doesn't produce zipper noise.
+    ///
+    /// # Arguments
+    /// * `node_id` - ID of the node
+    /// * `parameter` - Parameter name
+    /// * `value` - New parameter value
+    /// * `ramp_ms` - Ramp duration in milliseconds
+    pub fn set_parameter_smoothed(&mut self, node_id: &str, parameter: &str, value: f32, ramp_ms: f32) -> Result<()> {
+        let node = self.nodes.get_mut(node_id)
+            .ok_or_else(|| GraphError::NodeNotFound(node_id.to_string()))?;
+
+        node.set_parameter_smoothed(parameter, value, ramp_ms)
+            .map_err(|e| GraphError::ProcessingError(e.to_string()))
+    }
+
     /// Get a parameter from a node
     /// 
     /// # Arguments
@@ -180,6 +285,20 @@ impl AudioGraph {
     pub fn node_ids(&self) -> Vec<String> {
         self.nodes.keys().cloned().collect()
     }
+
+    /// Get the stable type tag of a node (see `AudioNode::node_type`)
+    pub fn node_type(&self, node_id: &str) -> Result<String> {
+        self.nodes.get(node_id)
+            .map(|n| n.node_type().to_string())
+            .ok_or_else(|| GraphError::NodeNotFound(node_id.to_string()))
+    }
+
+    /// Get the metadata (including parameter descriptors) of a node
+    pub fn node_metadata(&self, node_id: &str) -> Result<NodeMetadata> {
+        self.nodes.get(node_id)
+            .map(|n| n.metadata())
+            .ok_or_else(|| GraphError::NodeNotFound(node_id.to_string()))
+    }
     
     /// Get all connections in the graph
     pub fn connections(&self) -> &[Connection] {
@@ -263,35 +382,55 @@ impl AudioGraph {
         false
     }
     
-    /// Gather inputs for a node from all connected upstream nodes
+    /// Gather inputs for a node from all connected upstream nodes.
+    ///
+    /// Before summing, each incoming buffer is trimmed by whatever gain the
+    /// destination node exposes as an `input_<to_input>_gain` parameter (in
+    /// dB) - this is the only point in the graph where a per-input trim can
+    /// still act on one input in isolation, since every input is mixed into
+    /// a single buffer before `process` ever sees it. Nodes with no such
+    /// parameter (i.e. `get_parameter` returns `None`) are left untouched.
     fn gather_inputs(&self, node_id: &str) -> Result<AudioBuffer> {
         let incoming: Vec<&Connection> = self.connections
             .iter()
             .filter(|c| c.to_node == node_id)
             .collect();
-        
+
         if incoming.is_empty() {
             // No inputs, return silence
-            return Ok(AudioBuffer::silence(2, 48000, 512));
+            return Ok(AudioBuffer::silence(2, self.sample_rate, 512));
         }
-        
-        // Get first input
-        let first_buffer = self.buffer_cache
-            .get(&incoming[0].from_node)
-            .ok_or_else(|| GraphError::NodeNotFound(incoming[0].from_node.clone()))?;
-        
-        let mut mixed = first_buffer.clone();
-        
-        // Mix additional inputs
-        for conn in incoming.iter().skip(1) {
-            let buffer = self.buffer_cache
+
+        let destination = self.nodes.get(node_id);
+
+        let mut mixed: Option<AudioBuffer> = None;
+        for conn in &incoming {
+            let mut buffer = self.buffer_cache
                 .get(&conn.from_node)
-                .ok_or_else(|| GraphError::NodeNotFound(conn.from_node.clone()))?;
-            
-            mixed.mix(buffer);
+                .ok_or_else(|| GraphError::NodeNotFound(conn.from_node.clone()))?
+                .clone();
+
+            if let Some(gain_db) = destination
+                .and_then(|node| node.get_parameter(&format!("input_{}_gain", conn.to_input)))
+            {
+                buffer.apply_gain(db_to_linear(gain_db));
+            }
+
+            match mixed.as_mut() {
+                None => mixed = Some(buffer),
+                Some(existing) => {
+                    if buffer.sample_rate != existing.sample_rate {
+                        return Err(GraphError::SampleRateMismatch {
+                            expected: existing.sample_rate,
+                            actual: buffer.sample_rate,
+                        });
+                    }
+                    existing.mix(&buffer);
+                }
+            }
         }
-        
-        Ok(mixed)
+
+        Ok(mixed.expect("incoming is non-empty, checked above"))
     }
     
     /// Get final output from sink nodes
@@ -322,6 +461,12 @@ impl Default for AudioGraph {
     }
 }
 
+/// Convert a decibel value to a linear amplitude multiplier, for applying a
+/// node-exposed `input_N_gain` parameter to a buffer in `gather_inputs`.
+fn db_to_linear(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
 /// Thread-safe wrapper around AudioGraph
 pub type SharedAudioGraph = Arc<RwLock<AudioGraph>>;
 
@@ -335,7 +480,7 @@ mod tests {
         id: String,
         gain: f32,
     }
-    
+
     impl MockNode {
         fn new(id: &str) -> Self {
             Self {
@@ -349,7 +494,11 @@ mod tests {
         fn id(&self) -> &str {
             &self.id
         }
-        
+
+        fn node_type(&self) -> &str {
+            "MockNode"
+        }
+
         fn metadata(&self) -> NodeMetadata {
             NodeMetadata {
                 name: "Mock Node".to_string(),
@@ -375,7 +524,14 @@ mod tests {
                 Err(GraphError::ParameterNotFound(name.to_string()))
             }
         }
-        
+
+        fn set_parameter_smoothed(&mut self, name: &str, value: f32, ramp_ms: f32) -> Result<()> {
+            if ramp_ms < 0.0 {
+                return Err(GraphError::ProcessingError("ramp_ms must be non-negative".to_string()));
+            }
+            self.set_parameter(name, value)
+        }
+
         fn get_parameter(&self, name: &str) -> Option<f32> {
             if name == "gain" {
                 Some(self.gain)
@@ -384,7 +540,7 @@ mod tests {
             }
         }
     }
-    
+
     #[test]
     fn test_add_remove_node() {
         let mut graph = AudioGraph::new();
@@ -405,7 +561,31 @@ mod tests {
         
         assert!(matches!(result, Err(GraphError::NodeAlreadyExists(_))));
     }
-    
+
+    #[test]
+    fn test_set_parameter_smoothed_reaches_the_node() {
+        let mut graph = AudioGraph::new();
+        graph.add_node(Box::new(MockNode::new("node1"))).unwrap();
+
+        graph.set_parameter_smoothed("node1", "gain", 0.5, 20.0).unwrap();
+        assert_eq!(graph.get_parameter("node1", "gain").unwrap(), Some(0.5));
+
+        // A negative ramp is rejected by the node's own
+        // `set_parameter_smoothed`, proving the call actually reaches it
+        // rather than silently falling back to instant `set_parameter`.
+        let result = graph.set_parameter_smoothed("node1", "gain", 0.9, -1.0);
+        assert!(matches!(result, Err(GraphError::ProcessingError(_))));
+    }
+
+    #[test]
+    fn test_set_parameter_smoothed_rejects_unknown_node() {
+        let mut graph = AudioGraph::new();
+
+        let result = graph.set_parameter_smoothed("missing", "gain", 0.5, 20.0);
+
+        assert!(matches!(result, Err(GraphError::NodeNotFound(_))));
+    }
+
     #[test]
     fn test_connect_nodes() {
         let mut graph = AudioGraph::new();
@@ -419,6 +599,232 @@ mod tests {
         assert_eq!(graph.connections().len(), 1);
     }
     
+    /// Mock channel strip carrying `solo`/`mute` parameters, to exercise
+    /// `AudioGraph`'s solo-bus arbitration without depending on Orquestador
+    struct MockStrip {
+        id: String,
+        solo: bool,
+        mute: bool,
+    }
+
+    impl MockStrip {
+        fn new(id: &str) -> Self {
+            Self { id: id.to_string(), solo: false, mute: false }
+        }
+    }
+
+    impl AudioNode for MockStrip {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn node_type(&self) -> &str {
+            "MockStrip"
+        }
+
+        fn metadata(&self) -> NodeMetadata {
+            NodeMetadata {
+                name: "Mock Strip".to_string(),
+                category: NodeCategory::Routing,
+                input_channels: 2,
+                output_channels: 2,
+                parameters: vec![],
+                plugin: "test".to_string(),
+            }
+        }
+
+        // Acts as a constant-signal source (rather than passing through its
+        // actual input, which the graph always feeds as silence for a node
+        // with no incoming connections) so tests can tell a muted strip's
+        // contribution apart from an unmuted one.
+        fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+            let frames = input.num_frames().max(1);
+            if self.mute {
+                Ok(AudioBuffer::silence(input.channels, input.sample_rate, frames))
+            } else {
+                Ok(AudioBuffer {
+                    channels: input.channels,
+                    sample_rate: input.sample_rate,
+                    samples: vec![1.0; frames * input.channels],
+                })
+            }
+        }
+
+        fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+            match name {
+                "solo" => self.solo = value > 0.5,
+                "mute" => self.mute = value > 0.5,
+                _ => return Err(GraphError::ParameterNotFound(name.to_string())),
+            }
+            Ok(())
+        }
+
+        fn get_parameter(&self, name: &str) -> Option<f32> {
+            match name {
+                "solo" => Some(if self.solo { 1.0 } else { 0.0 }),
+                "mute" => Some(if self.mute { 1.0 } else { 0.0 }),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_solo_mutes_unsoloed_sibling_on_same_bus() {
+        let mut graph = AudioGraph::new();
+
+        graph.add_node(Box::new(MockStrip::new("strip1"))).unwrap();
+        graph.add_node(Box::new(MockStrip::new("strip2"))).unwrap();
+        graph.add_node(Box::new(MockNode::new("bus"))).unwrap();
+
+        graph.connect(Connection::simple("strip1".to_string(), "bus".to_string())).unwrap();
+        graph.connect(Connection::simple("strip2".to_string(), "bus".to_string())).unwrap();
+
+        graph.set_parameter("strip1", "solo", 1.0).unwrap();
+        assert!(graph.solo_active());
+
+        let output = graph.process(AudioBuffer::new(2, 48000, 4)).unwrap();
+        // "bus" sums strip1 (soloed, contributes its signal) and strip2
+        // (forced silent by solo arbitration), so only strip1 comes through.
+        // Both strips have no incoming connection, so each is fed the graph's
+        // 512-frame stereo silence rather than the 4-frame input above.
+        assert_eq!(output.samples, vec![1.0; 512 * 2]);
+
+        // The forced mute must not leak into strip2's actual mute state.
+        assert_eq!(graph.get_parameter("strip2", "mute").unwrap(), Some(0.0));
+    }
+
+    #[test]
+    fn test_no_solo_active_leaves_strips_untouched() {
+        let mut graph = AudioGraph::new();
+
+        graph.add_node(Box::new(MockStrip::new("strip1"))).unwrap();
+        graph.add_node(Box::new(MockNode::new("bus"))).unwrap();
+        graph.connect(Connection::simple("strip1".to_string(), "bus".to_string())).unwrap();
+
+        assert!(!graph.solo_active());
+        assert_eq!(graph.get_parameter("strip1", "mute").unwrap(), Some(0.0));
+    }
+
+    #[test]
+    fn test_process_rejects_mismatched_sample_rate() {
+        let mut graph = AudioGraph::with_sample_rate(48000);
+        graph.add_node(Box::new(MockNode::new("node1"))).unwrap();
+
+        let input = AudioBuffer::new(2, 44100, 4);
+        let result = graph.process(input);
+
+        assert!(matches!(
+            result,
+            Err(GraphError::SampleRateMismatch { expected: 48000, actual: 44100 })
+        ));
+    }
+
+    #[test]
+    fn test_gather_inputs_rejects_mismatched_upstream_rates() {
+        let mut graph = AudioGraph::with_sample_rate(48000);
+
+        graph.add_node(Box::new(MockNode::new("node1"))).unwrap();
+        graph.add_node(Box::new(MockNode::new("node2"))).unwrap();
+        graph.add_node(Box::new(MockNode::new("bus"))).unwrap();
+
+        graph.connect(Connection::simple("node1".to_string(), "bus".to_string())).unwrap();
+        graph.connect(Connection::simple("node2".to_string(), "bus".to_string())).unwrap();
+
+        // Simulate an upstream node that (incorrectly) emitted a different
+        // rate than the rest of the graph.
+        graph.buffer_cache.insert("node1".to_string(), AudioBuffer::new(2, 48000, 4));
+        graph.buffer_cache.insert("node2".to_string(), AudioBuffer::new(2, 44100, 4));
+
+        let result = graph.gather_inputs("bus");
+        assert!(matches!(
+            result,
+            Err(GraphError::SampleRateMismatch { expected: 48000, actual: 44100 })
+        ));
+    }
+
+    /// A mock bus node exposing `input_N_gain` parameters (in dB), like
+    /// `MixerNode`, so `gather_inputs` has something to read and apply.
+    struct MockTrimBus {
+        id: String,
+        input_gains_db: Vec<f32>,
+    }
+
+    impl MockTrimBus {
+        fn new(id: &str, num_inputs: usize) -> Self {
+            Self {
+                id: id.to_string(),
+                input_gains_db: vec![0.0; num_inputs],
+            }
+        }
+    }
+
+    impl AudioNode for MockTrimBus {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn node_type(&self) -> &str {
+            "MockTrimBus"
+        }
+
+        fn metadata(&self) -> NodeMetadata {
+            NodeMetadata {
+                name: "Mock Trim Bus".to_string(),
+                category: NodeCategory::Routing,
+                input_channels: 2,
+                output_channels: 2,
+                parameters: vec![],
+                plugin: "test".to_string(),
+            }
+        }
+
+        fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+            Ok(input.clone())
+        }
+
+        fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+            if let Some(rest) = name.strip_prefix("input_").and_then(|r| r.strip_suffix("_gain")) {
+                if let Ok(index) = rest.parse::<usize>() {
+                    if let Some(gain) = self.input_gains_db.get_mut(index) {
+                        *gain = value;
+                        return Ok(());
+                    }
+                }
+            }
+            Err(GraphError::ParameterNotFound(name.to_string()))
+        }
+
+        fn get_parameter(&self, name: &str) -> Option<f32> {
+            let rest = name.strip_prefix("input_")?.strip_suffix("_gain")?;
+            let index = rest.parse::<usize>().ok()?;
+            self.input_gains_db.get(index).copied()
+        }
+    }
+
+    #[test]
+    fn test_gather_inputs_applies_destination_per_input_gain() {
+        let mut graph = AudioGraph::with_sample_rate(48000);
+
+        graph.add_node(Box::new(MockNode::new("node1"))).unwrap();
+        graph.add_node(Box::new(MockNode::new("node2"))).unwrap();
+        graph.add_node(Box::new(MockTrimBus::new("bus", 2))).unwrap();
+
+        graph.connect(Connection::new("node1".to_string(), 0, "bus".to_string(), 0)).unwrap();
+        graph.connect(Connection::new("node2".to_string(), 0, "bus".to_string(), 1)).unwrap();
+
+        // Mute input 1 entirely (-inf-ish dB), leave input 0 untouched.
+        graph.set_parameter("bus", "input_1_gain", -60.0).unwrap();
+
+        graph.buffer_cache.insert("node1".to_string(), AudioBuffer { channels: 1, sample_rate: 48000, samples: vec![1.0; 4] });
+        graph.buffer_cache.insert("node2".to_string(), AudioBuffer { channels: 1, sample_rate: 48000, samples: vec![1.0; 4] });
+
+        let mixed = graph.gather_inputs("bus").unwrap();
+        let expected_contribution = db_to_linear(-60.0);
+        for &sample in &mixed.samples {
+            assert!((sample - (1.0 + expected_contribution)).abs() < 1e-4);
+        }
+    }
+
     #[test]
     fn test_cycle_detection() {
         let mut graph = AudioGraph::new();
@@ -0,0 +1,232 @@
+use crate::{AudioGraph, AudioNode, Connection, GraphError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Current schema version for `GraphDocument`. Bump on breaking format changes.
+pub const GRAPH_DOCUMENT_VERSION: u32 = 1;
+
+/// Serialized representation of a single node: its stable type tag, ID, and
+/// current parameter values.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SerializedNode {
+    /// Unique node identifier
+    pub id: String,
+
+    /// Plugin that provides this node (e.g. `"compositor"`, `"orquestador"`),
+    /// taken from `NodeMetadata::plugin`
+    pub plugin: String,
+
+    /// Stable type tag (see `AudioNode::node_type`), e.g. `"EqualizerNode"`
+    pub node_type: String,
+
+    /// Parameter values at the time of serialization, keyed by parameter name
+    pub parameters: HashMap<String, f32>,
+}
+
+/// Serializable snapshot of an `AudioGraph`: its nodes (by type tag and
+/// parameters) and the connections between them.
+///
+/// Reconstructing a graph from a `GraphDocument` requires a `NodeFactory`
+/// capable of turning each node's `node_type` tag back into a concrete
+/// `AudioNode`, since `espectro` itself has no knowledge of the node types
+/// defined by plugins such as Compositor or Orquestador.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GraphDocument {
+    /// Schema version this document was written with
+    pub schema_version: u32,
+
+    /// All nodes in the graph
+    pub nodes: Vec<SerializedNode>,
+
+    /// All connections between nodes
+    pub connections: Vec<Connection>,
+}
+
+/// Builds concrete `AudioNode` instances from a stable type tag.
+///
+/// Implemented on the application side (where all plugin crates are
+/// available) and passed to `GraphDocument::apply_to_graph` to reconstruct
+/// a graph that was previously captured with `GraphDocument::from_graph`.
+pub trait NodeFactory {
+    /// Construct a new node of `node_type` with the given `id`.
+    ///
+    /// `parameters` is the full parameter map from the `SerializedNode`,
+    /// passed in *before* construction (not just applied via `set_parameter`
+    /// afterward) so the factory can recover structural constructor
+    /// arguments - like an equalizer's band count or a mixer's input count -
+    /// that determine which parameter names even exist on the node. Without
+    /// this, a node rebuilt with the wrong structural size would reject
+    /// every `band_N_*`/`input_N_*` parameter past its default size as
+    /// `ParameterNotFound`.
+    ///
+    /// # Errors
+    /// Returns `GraphError::UnknownNodeType` if `node_type` isn't recognized.
+    fn create(
+        &self,
+        node_type: &str,
+        id: String,
+        parameters: &HashMap<String, f32>,
+    ) -> Result<Box<dyn AudioNode>>;
+}
+
+impl GraphDocument {
+    /// Capture the current state of an `AudioGraph` as a `GraphDocument`.
+    pub fn from_graph(graph: &AudioGraph) -> Result<Self> {
+        let mut nodes = Vec::new();
+
+        for id in graph.node_ids() {
+            let node_type = graph.node_type(&id)?;
+            let metadata = graph.node_metadata(&id)?;
+
+            let mut parameters = HashMap::new();
+            for param in &metadata.parameters {
+                if let Some(value) = graph.get_parameter(&id, &param.name)? {
+                    parameters.insert(param.name.clone(), value);
+                }
+            }
+
+            nodes.push(SerializedNode { id, plugin: metadata.plugin, node_type, parameters });
+        }
+
+        Ok(Self {
+            schema_version: GRAPH_DOCUMENT_VERSION,
+            nodes,
+            connections: graph.connections().to_vec(),
+        })
+    }
+
+    /// Rebuild this document's nodes and connections into `graph`, using
+    /// `factory` to construct each node from its `node_type` tag.
+    ///
+    /// # Errors
+    /// * `GraphError::UnknownNodeType` if a node's type isn't recognized by `factory`
+    /// * `GraphError::NodeAlreadyExists` if `graph` already contains a node with a matching ID
+    /// * `GraphError::CycleDetected` if the document's connections form a cycle
+    pub fn apply_to_graph(&self, graph: &mut AudioGraph, factory: &dyn NodeFactory) -> Result<()> {
+        for serialized in &self.nodes {
+            let mut node =
+                factory.create(&serialized.node_type, serialized.id.clone(), &serialized.parameters)?;
+
+            for (name, value) in &serialized.parameters {
+                node.set_parameter(name, *value)
+                    .map_err(|e| GraphError::ProcessingError(e.to_string()))?;
+            }
+
+            graph.add_node(node)?;
+        }
+
+        for connection in &self.connections {
+            graph.connect(connection.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AudioBuffer, NodeCategory, NodeMetadata, ParameterDescriptor};
+
+    struct MockNode {
+        id: String,
+        gain: f32,
+    }
+
+    impl AudioNode for MockNode {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        fn node_type(&self) -> &str {
+            "MockNode"
+        }
+
+        fn metadata(&self) -> NodeMetadata {
+            NodeMetadata {
+                name: "Mock Node".to_string(),
+                category: NodeCategory::Effect,
+                input_channels: 2,
+                output_channels: 2,
+                parameters: vec![ParameterDescriptor::new("gain", 0.0, 2.0, 1.0, "x", "Gain")],
+                plugin: "test".to_string(),
+            }
+        }
+
+        fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+            let mut output = input.clone();
+            output.apply_gain(self.gain);
+            Ok(output)
+        }
+
+        fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+            if name == "gain" {
+                self.gain = value;
+                Ok(())
+            } else {
+                Err(GraphError::ParameterNotFound(name.to_string()))
+            }
+        }
+
+        fn get_parameter(&self, name: &str) -> Option<f32> {
+            if name == "gain" {
+                Some(self.gain)
+            } else {
+                None
+            }
+        }
+    }
+
+    struct MockFactory;
+
+    impl NodeFactory for MockFactory {
+        fn create(
+            &self,
+            node_type: &str,
+            id: String,
+            _parameters: &HashMap<String, f32>,
+        ) -> Result<Box<dyn AudioNode>> {
+            match node_type {
+                "MockNode" => Ok(Box::new(MockNode { id, gain: 1.0 })),
+                other => Err(GraphError::UnknownNodeType(other.to_string())),
+            }
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut graph = AudioGraph::new();
+        graph.add_node(Box::new(MockNode { id: "node1".to_string(), gain: 0.5 })).unwrap();
+        graph.add_node(Box::new(MockNode { id: "node2".to_string(), gain: 1.0 })).unwrap();
+        graph.connect(Connection::simple("node1".to_string(), "node2".to_string())).unwrap();
+
+        let doc = GraphDocument::from_graph(&graph).unwrap();
+        assert_eq!(doc.nodes.len(), 2);
+        assert_eq!(doc.connections.len(), 1);
+
+        let mut rebuilt = AudioGraph::new();
+        doc.apply_to_graph(&mut rebuilt, &MockFactory).unwrap();
+
+        assert_eq!(rebuilt.node_ids().len(), 2);
+        assert_eq!(rebuilt.connections().len(), 1);
+        assert_eq!(rebuilt.get_parameter("node1", "gain").unwrap(), Some(0.5));
+    }
+
+    #[test]
+    fn test_unknown_node_type() {
+        let doc = GraphDocument {
+            schema_version: GRAPH_DOCUMENT_VERSION,
+            nodes: vec![SerializedNode {
+                id: "node1".to_string(),
+                plugin: "test".to_string(),
+                node_type: "NotARealNode".to_string(),
+                parameters: HashMap::new(),
+            }],
+            connections: vec![],
+        };
+
+        let mut graph = AudioGraph::new();
+        let result = doc.apply_to_graph(&mut graph, &MockFactory);
+        assert!(matches!(result, Err(GraphError::UnknownNodeType(_))));
+    }
+}
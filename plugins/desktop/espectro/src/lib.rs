@@ -9,9 +9,13 @@ pub mod node;
 pub mod graph;
 pub mod connection;
 pub mod error;
+pub mod graph_document;
+pub mod channel_layout;
 
 pub use buffer::AudioBuffer;
-pub use node::{AudioNode, NodeMetadata, NodeCategory, ParameterDescriptor};
+pub use node::{AudioNode, NodeMetadata, NodeCategory, ParameterDescriptor, SmoothedParameter};
 pub use graph::AudioGraph;
 pub use connection::Connection;
 pub use error::{GraphError, Result};
+pub use graph_document::{GraphDocument, NodeFactory, SerializedNode};
+pub use channel_layout::{ChannelLayout, ChannelRole};
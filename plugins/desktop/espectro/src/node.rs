@@ -34,9 +34,14 @@ pub struct ParameterDescriptor {
     
     /// Unit of measurement (e.g., "dB", "Hz", "%", "ms")
     pub unit: String,
-    
+
     /// Human-readable label
     pub label: String,
+
+    /// When set, changes to this parameter should be ramped over this many
+    /// milliseconds (see `SmoothedParameter`) instead of applied instantly,
+    /// so automating it doesn't produce zipper noise.
+    pub smoothing_ms: Option<f32>,
 }
 
 impl ParameterDescriptor {
@@ -49,15 +54,97 @@ impl ParameterDescriptor {
             default,
             unit: unit.to_string(),
             label: label.to_string(),
+            smoothing_ms: None,
         }
     }
-    
+
+    /// Mark this parameter as smoothed, recording `smoothing_ms` as its ramp
+    /// time on the descriptor. This only sets metadata: `set_parameter`
+    /// still applies changes instantly, and ramping goes through
+    /// `set_parameter_smoothed`
+    pub fn with_smoothing(mut self, smoothing_ms: f32) -> Self {
+        self.smoothing_ms = Some(smoothing_ms);
+        self
+    }
+
     /// Clamp a value to the parameter's range
     pub fn clamp(&self, value: f32) -> f32 {
         value.clamp(self.min, self.max)
     }
 }
 
+/// Ramps a parameter from its current value toward a target instead of
+/// snapping instantly, eliminating the "zipper noise" audible when a
+/// parameter changes mid-buffer. Nodes that want glitch-free automation hold
+/// one of these per parameter instead of a bare `f32`.
+///
+/// Uses a one-pole smoothing filter: `current += (target - current) * (1 -
+/// a)` per sample, where `a = exp(-1 / (smoothing_ms/1000 * sample_rate))`.
+/// Call `prepare` once per `process` block (the coefficient only depends on
+/// sample rate and ramp time, not on the sample position), then `next` once
+/// per sample.
+#[derive(Clone, Debug)]
+pub struct SmoothedParameter {
+    current: f32,
+    target: f32,
+    smoothing_ms: f32,
+    coefficient: f32,
+}
+
+impl SmoothedParameter {
+    /// Create a parameter starting at `initial`, ramping over `smoothing_ms`
+    /// milliseconds when changed. `smoothing_ms <= 0.0` disables smoothing:
+    /// `next` jumps straight to the target.
+    pub fn new(initial: f32, smoothing_ms: f32) -> Self {
+        Self {
+            current: initial,
+            target: initial,
+            smoothing_ms,
+            coefficient: 0.0,
+        }
+    }
+
+    /// Snap instantly to `value`, bypassing the ramp
+    pub fn set_immediate(&mut self, value: f32) {
+        self.current = value;
+        self.target = value;
+    }
+
+    /// Ramp toward `value` over this parameter's configured `smoothing_ms`
+    pub fn set_target(&mut self, value: f32) {
+        self.target = value;
+    }
+
+    /// Override the ramp time used by subsequent `set_target` calls
+    pub fn set_smoothing_ms(&mut self, smoothing_ms: f32) {
+        self.smoothing_ms = smoothing_ms;
+    }
+
+    /// The value last set via `set_target`/`set_immediate`, i.e. what a
+    /// caller reading the parameter back should see (not the mid-ramp value)
+    pub fn target(&self) -> f32 {
+        self.target
+    }
+
+    /// Recompute the one-pole coefficient for `sample_rate`. Cheap enough to
+    /// call once per block; avoids recomputing `exp()` every sample.
+    pub fn prepare(&mut self, sample_rate: u32) {
+        self.coefficient = if self.smoothing_ms <= 0.0 || sample_rate == 0 {
+            0.0
+        } else {
+            let tau_samples = self.smoothing_ms / 1000.0 * sample_rate as f32;
+            (-1.0 / tau_samples).exp()
+        };
+    }
+
+    /// Advance one sample toward the target, returning the new interpolated
+    /// value
+    pub fn next(&mut self) -> f32 {
+        self.current += (self.target - self.current) * (1.0 - self.coefficient);
+        self.current
+    }
+}
+
 /// Metadata describing a node's capabilities
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct NodeMetadata {
@@ -87,7 +174,12 @@ pub struct NodeMetadata {
 pub trait AudioNode: Send + Sync {
     /// Unique node identifier
     fn id(&self) -> &str;
-    
+
+    /// Stable type tag identifying the concrete node implementation (e.g.
+    /// `"EqualizerNode"`), independent of its human-readable `metadata().name`.
+    /// Used to reconstruct a node from a `GraphDocument`.
+    fn node_type(&self) -> &str;
+
     /// Node metadata
     fn metadata(&self) -> NodeMetadata;
     
@@ -101,12 +193,22 @@ pub trait AudioNode: Send + Sync {
     fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer>;
     
     /// Handle parameter changes from UI
-    /// 
+    ///
     /// # Arguments
     /// * `name` - Parameter name
     /// * `value` - New parameter value
     fn set_parameter(&mut self, name: &str, value: f32) -> Result<()>;
-    
+
+    /// Like `set_parameter`, but ramps to the new value over `ramp_ms`
+    /// milliseconds instead of snapping instantly, avoiding zipper noise
+    /// when a parameter is automated. The default falls back to an instant
+    /// `set_parameter` call for nodes that haven't opted into per-sample
+    /// smoothing (see `SmoothedParameter`).
+    fn set_parameter_smoothed(&mut self, name: &str, value: f32, ramp_ms: f32) -> Result<()> {
+        let _ = ramp_ms;
+        self.set_parameter(name, value)
+    }
+
     /// Get current parameter value
     /// 
     /// # Arguments
@@ -147,4 +249,40 @@ mod tests {
         let category = NodeCategory::Effect;
         assert_eq!(category, NodeCategory::Effect);
     }
+
+    #[test]
+    fn test_smoothed_parameter_ramps_towards_target() {
+        let mut param = SmoothedParameter::new(0.0, 10.0);
+        param.set_target(1.0);
+        param.prepare(48000);
+
+        let first = param.next();
+        assert!(first > 0.0 && first < 1.0);
+
+        // Many samples later it should have converged close to the target
+        let mut last = first;
+        for _ in 0..48000 {
+            last = param.next();
+        }
+        assert!((last - 1.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_smoothed_parameter_set_immediate_skips_ramp() {
+        let mut param = SmoothedParameter::new(0.0, 50.0);
+        param.set_immediate(1.0);
+        param.prepare(48000);
+
+        assert_eq!(param.next(), 1.0);
+        assert_eq!(param.target(), 1.0);
+    }
+
+    #[test]
+    fn test_smoothed_parameter_disabled_jumps_instantly() {
+        let mut param = SmoothedParameter::new(0.0, 0.0);
+        param.set_target(1.0);
+        param.prepare(48000);
+
+        assert_eq!(param.next(), 1.0);
+    }
 }
@@ -1,5 +1,4 @@
-use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
-use std::f32::consts::FRAC_PI_4;
+use espectro::{AudioNode, AudioBuffer, ChannelLayout, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
 
 /// Channel strip node combining volume, pan, mute, and solo
 /// 
@@ -35,18 +34,17 @@ impl ChannelStripNode {
     fn update_gain(&mut self) {
         self.gain_linear = Self::db_to_linear(self.gain_db);
     }
-    
-    fn calculate_pan_gains(&self) -> (f32, f32) {
-        let pan_angle = (self.pan + 1.0) * FRAC_PI_4;
-        (pan_angle.cos(), pan_angle.sin())
-    }
 }
 
 impl AudioNode for ChannelStripNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "ChannelStripNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
         NodeMetadata {
             name: "Channel Strip".to_string(),
@@ -98,17 +96,17 @@ impl AudioNode for ChannelStripNode {
         }
         
         let mut output = input.clone();
-        let (left_pan, right_pan) = self.calculate_pan_gains();
-        
-        // Apply gain and pan
-        for i in (0..output.samples.len()).step_by(2) {
-            let left = output.samples[i];
-            let right = output.samples[i + 1];
-            
-            output.samples[i] = left * self.gain_linear * left_pan;
-            output.samples[i + 1] = right * self.gain_linear * right_pan;
+        let layout = ChannelLayout::from_channel_count(output.channels).unwrap_or(ChannelLayout::Stereo);
+        let pan_gains = layout.pan_gains(self.pan);
+
+        // Apply gain and pan, one frame (one sample per channel) at a time
+        for frame in output.samples.chunks_mut(output.channels) {
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                let pan_gain = pan_gains.get(channel).copied().unwrap_or(1.0);
+                *sample *= self.gain_linear * pan_gain;
+            }
         }
-        
+
         Ok(output)
     }
     
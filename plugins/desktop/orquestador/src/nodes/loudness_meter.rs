@@ -0,0 +1,391 @@
+use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, Result};
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+/// EBU R128 / ITU-R BS.1770 loudness meter
+///
+/// Taps the signal passing through it (the output is identical to the input)
+/// and exposes momentary, short-term, and integrated loudness plus true peak
+/// through `get_parameter`, so the host UI can drive a broadcast-style meter.
+pub struct LoudnessMeterNode {
+    id: String,
+    sample_rate: f32,
+
+    /// 100 ms hop accumulators, one per channel
+    hop_accumulators: Vec<f32>,
+    hop_samples_seen: usize,
+    hop_size: usize,
+
+    /// One sliding 400 ms window (4 hops, 75% overlap) energy per completed
+    /// 100 ms hop, across the whole measurement (used for integrated
+    /// loudness gating, per BS.1770)
+    all_blocks: Vec<f32>,
+
+    /// Recent blocks only, used for momentary (400 ms = 4 blocks) and
+    /// short-term (3 s = 30 blocks) windows
+    recent_blocks: VecDeque<f32>,
+
+    k_weight: Vec<KWeightingFilter>,
+
+    true_peak: f32,
+    sample_peak: f32,
+
+    lufs_momentary: f32,
+    lufs_short: f32,
+}
+
+/// Two-stage K-weighting pre-filter (RLB high-pass + high-shelf) from BS.1770
+struct KWeightingFilter {
+    shelf: Biquad,
+    highpass: Biquad,
+}
+
+impl KWeightingFilter {
+    fn new(sample_rate: f32) -> Self {
+        let mut shelf = Biquad::new();
+        // Stage 1: high-shelf, +4 dB around 1681 Hz
+        shelf.update_high_shelf(1681.0, 4.0, 0.7071, sample_rate);
+
+        let mut highpass = Biquad::new();
+        // Stage 2: high-pass (RLB), ~38 Hz
+        highpass.update_high_pass(38.0, 0.5, sample_rate);
+
+        Self { shelf, highpass }
+    }
+
+    fn process(&mut self, sample: f32) -> f32 {
+        self.highpass.process(self.shelf.process(sample))
+    }
+}
+
+/// Minimal single-channel biquad, used only for K-weighting here
+struct Biquad {
+    a0: f32,
+    a1: f32,
+    a2: f32,
+    b1: f32,
+    b2: f32,
+    x1: f32,
+    x2: f32,
+    y1: f32,
+    y2: f32,
+}
+
+impl Biquad {
+    fn new() -> Self {
+        Self { a0: 1.0, a1: 0.0, a2: 0.0, b1: 0.0, b2: 0.0, x1: 0.0, x2: 0.0, y1: 0.0, y2: 0.0 }
+    }
+
+    fn set_coeffs(&mut self, b0: f32, b1: f32, b2: f32, a0: f32, a1: f32, a2: f32) {
+        self.a0 = b0 / a0;
+        self.a1 = b1 / a0;
+        self.a2 = b2 / a0;
+        self.b1 = a1 / a0;
+        self.b2 = a2 / a0;
+    }
+
+    fn update_high_shelf(&mut self, freq: f32, gain_db: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let cos_w0 = w0.cos();
+        let a = 10.0_f32.powf(gain_db / 40.0);
+        let alpha = w0.sin() / (2.0 * q);
+        let two_sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let b0 = a * ((a + 1.0) + (a - 1.0) * cos_w0 + two_sqrt_a_alpha);
+        let b1 = -2.0 * a * ((a - 1.0) + (a + 1.0) * cos_w0);
+        let b2 = a * ((a + 1.0) + (a - 1.0) * cos_w0 - two_sqrt_a_alpha);
+        let a0 = (a + 1.0) - (a - 1.0) * cos_w0 + two_sqrt_a_alpha;
+        let a1 = 2.0 * ((a - 1.0) - (a + 1.0) * cos_w0);
+        let a2 = (a + 1.0) - (a - 1.0) * cos_w0 - two_sqrt_a_alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn update_high_pass(&mut self, freq: f32, q: f32, sample_rate: f32) {
+        let w0 = 2.0 * PI * freq / sample_rate;
+        let alpha = w0.sin() / (2.0 * q);
+        let cos_w0 = w0.cos();
+
+        let b0 = (1.0 + cos_w0) / 2.0;
+        let b1 = -(1.0 + cos_w0);
+        let b2 = (1.0 + cos_w0) / 2.0;
+        let a0 = 1.0 + alpha;
+        let a1 = -2.0 * cos_w0;
+        let a2 = 1.0 - alpha;
+
+        self.set_coeffs(b0, b1, b2, a0, a1, a2);
+    }
+
+    fn process(&mut self, x: f32) -> f32 {
+        let y = self.a0 * x + self.a1 * self.x1 + self.a2 * self.x2
+            - self.b1 * self.y1 - self.b2 * self.y2;
+        self.x2 = self.x1;
+        self.x1 = x;
+        self.y2 = self.y1;
+        self.y1 = y;
+        y
+    }
+}
+
+/// Channel weight per BS.1770: channels 0 and 1 (L/R) get unity weight and every
+/// further channel gets the 1.41x (+1.5 dB) surround weight; no layout is
+/// consulted, so center and LFE are weighted as surrounds too
+fn channel_weight(channel: usize, channels: usize) -> f32 {
+    if channels <= 2 || channel < 2 {
+        1.0
+    } else {
+        1.41
+    }
+}
+
+const MOMENTARY_BLOCKS: usize = 4; // 400 ms / 100 ms hop
+const SHORT_TERM_BLOCKS: usize = 30; // 3 s / 100 ms hop
+const ABSOLUTE_GATE_LUFS: f32 = -70.0;
+const RELATIVE_GATE_OFFSET: f32 = -10.0;
+const OVERSAMPLE_FACTOR: usize = 4;
+
+impl LoudnessMeterNode {
+    /// Create a new loudness meter tapping a stereo (or multichannel) bus
+    pub fn new(id: String) -> Self {
+        Self::with_sample_rate(id, 48000.0)
+    }
+
+    fn with_sample_rate(id: String, sample_rate: f32) -> Self {
+        let hop_size = (sample_rate * 0.1) as usize;
+        Self {
+            id,
+            sample_rate,
+            hop_accumulators: Vec::new(),
+            hop_samples_seen: 0,
+            hop_size: hop_size.max(1),
+            all_blocks: Vec::new(),
+            recent_blocks: VecDeque::with_capacity(SHORT_TERM_BLOCKS),
+            k_weight: Vec::new(),
+            true_peak: 0.0,
+            sample_peak: 0.0,
+            lufs_momentary: f32::NEG_INFINITY,
+            lufs_short: f32::NEG_INFINITY,
+        }
+    }
+
+    fn ensure_channels(&mut self, channels: usize) {
+        if self.k_weight.len() != channels {
+            self.k_weight = (0..channels).map(|_| KWeightingFilter::new(self.sample_rate)).collect();
+            self.hop_accumulators = vec![0.0; channels];
+            self.hop_samples_seen = 0;
+        }
+    }
+
+    /// Oversample a short run of samples by `OVERSAMPLE_FACTOR` (linear
+    /// interpolation) and return the peak absolute value found
+    fn true_peak_of(window: &[f32; 2]) -> f32 {
+        let mut peak = window[0].abs().max(window[1].abs());
+        for step in 1..OVERSAMPLE_FACTOR {
+            let t = step as f32 / OVERSAMPLE_FACTOR as f32;
+            let interpolated = window[0] + (window[1] - window[0]) * t;
+            peak = peak.max(interpolated.abs());
+        }
+        peak
+    }
+
+    fn finish_hop(&mut self) {
+        let weighted_sum: f32 = self.hop_accumulators
+            .iter()
+            .enumerate()
+            .map(|(ch, &sum)| {
+                let mean_square = sum / self.hop_size as f32;
+                mean_square * channel_weight(ch, self.hop_accumulators.len())
+            })
+            .sum();
+
+        self.recent_blocks.push_back(weighted_sum);
+        while self.recent_blocks.len() > SHORT_TERM_BLOCKS {
+            self.recent_blocks.pop_front();
+        }
+
+        if self.recent_blocks.len() >= MOMENTARY_BLOCKS {
+            let window_energy: f32 = self.recent_blocks.iter().rev().take(MOMENTARY_BLOCKS).sum::<f32>()
+                / MOMENTARY_BLOCKS as f32;
+            self.all_blocks.push(window_energy);
+        }
+
+        self.lufs_momentary = Self::average_to_lufs(self.recent_blocks.iter().rev().take(MOMENTARY_BLOCKS));
+        self.lufs_short = Self::average_to_lufs(self.recent_blocks.iter());
+
+        for acc in &mut self.hop_accumulators {
+            *acc = 0.0;
+        }
+        self.hop_samples_seen = 0;
+    }
+
+    fn average_to_lufs<'a>(blocks: impl Iterator<Item = &'a f32>) -> f32 {
+        let (sum, count) = blocks.fold((0.0, 0usize), |(sum, count), &v| (sum + v, count + 1));
+        if count == 0 || sum <= 0.0 {
+            f32::NEG_INFINITY
+        } else {
+            -0.691 + 10.0 * (sum / count as f32).log10()
+        }
+    }
+
+    /// Two-stage gated integrated loudness, per BS.1770 / EBU R128
+    fn integrated_lufs(&self) -> f32 {
+        if self.all_blocks.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let absolute_gate_energy = 10.0_f32.powf((ABSOLUTE_GATE_LUFS + 0.691) / 10.0);
+        let survivors: Vec<f32> = self.all_blocks.iter().copied()
+            .filter(|&e| e > absolute_gate_energy)
+            .collect();
+
+        if survivors.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let mean_energy: f32 = survivors.iter().sum::<f32>() / survivors.len() as f32;
+        let relative_gate_lufs = -0.691 + 10.0 * mean_energy.log10() + RELATIVE_GATE_OFFSET;
+        let relative_gate_energy = 10.0_f32.powf((relative_gate_lufs + 0.691) / 10.0);
+
+        let gated: Vec<f32> = survivors.into_iter().filter(|&e| e > relative_gate_energy).collect();
+        if gated.is_empty() {
+            return f32::NEG_INFINITY;
+        }
+
+        let gated_mean = gated.iter().sum::<f32>() / gated.len() as f32;
+        -0.691 + 10.0 * gated_mean.log10()
+    }
+}
+
+impl AudioNode for LoudnessMeterNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> &str {
+        "LoudnessMeterNode"
+    }
+
+    fn metadata(&self) -> NodeMetadata {
+        NodeMetadata {
+            name: "Loudness Meter".to_string(),
+            category: NodeCategory::Effect,
+            input_channels: 2,
+            output_channels: 2,
+            parameters: vec![], // measurement-only, all outputs are read via get_parameter
+            plugin: "orquestador".to_string(),
+        }
+    }
+
+    fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+        if self.sample_rate != input.sample_rate as f32 {
+            *self = Self::with_sample_rate(self.id.clone(), input.sample_rate as f32);
+        }
+        self.ensure_channels(input.channels);
+
+        let frames = input.num_frames();
+        let mut frame = 0;
+        while frame < frames {
+            let base = frame * input.channels;
+
+            for ch in 0..input.channels {
+                let sample = input.samples[base + ch];
+                self.sample_peak = self.sample_peak.max(sample.abs());
+
+                let weighted = self.k_weight[ch].process(sample);
+                self.hop_accumulators[ch] += weighted * weighted;
+            }
+
+            if input.channels >= 2 {
+                let next = (frame + 1).min(frames - 1);
+                let next_base = next * input.channels;
+                for ch in 0..input.channels {
+                    let window = [input.samples[base + ch], input.samples[next_base + ch]];
+                    self.true_peak = self.true_peak.max(Self::true_peak_of(&window));
+                }
+            }
+
+            self.hop_samples_seen += 1;
+            if self.hop_samples_seen >= self.hop_size {
+                self.finish_hop();
+            }
+
+            frame += 1;
+        }
+
+        Ok(input.clone())
+    }
+
+    fn set_parameter(&mut self, _name: &str, _value: f32) -> Result<()> {
+        // Read-only measurement node
+        Ok(())
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        match name {
+            "lufs_momentary" => Some(self.lufs_momentary),
+            "lufs_short" => Some(self.lufs_short),
+            "lufs_integrated" => Some(self.integrated_lufs()),
+            "true_peak_db" => Some(20.0 * self.true_peak.max(1e-9).log10()),
+            "sample_peak_db" => Some(20.0 * self.sample_peak.max(1e-9).log10()),
+            _ => None,
+        }
+    }
+
+    fn reset(&mut self) {
+        for filter in &mut self.k_weight {
+            filter.shelf.x1 = 0.0;
+            filter.shelf.x2 = 0.0;
+            filter.shelf.y1 = 0.0;
+            filter.shelf.y2 = 0.0;
+            filter.highpass.x1 = 0.0;
+            filter.highpass.x2 = 0.0;
+            filter.highpass.y1 = 0.0;
+            filter.highpass.y2 = 0.0;
+        }
+        self.hop_accumulators.iter_mut().for_each(|a| *a = 0.0);
+        self.hop_samples_seen = 0;
+        self.all_blocks.clear();
+        self.recent_blocks.clear();
+        self.true_peak = 0.0;
+        self.sample_peak = 0.0;
+        self.lufs_momentary = f32::NEG_INFINITY;
+        self.lufs_short = f32::NEG_INFINITY;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_meter_passthrough() {
+        let mut meter = LoudnessMeterNode::new("meter1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 512);
+        input.samples.iter_mut().enumerate().for_each(|(i, s)| *s = 0.3 * (i as f32 * 0.01).sin());
+
+        let output = meter.process(&input).unwrap();
+        assert_eq!(output.samples, input.samples);
+    }
+
+    #[test]
+    fn test_meter_silence_reports_negative_infinity() {
+        let mut meter = LoudnessMeterNode::new("meter1".to_string());
+
+        let input = AudioBuffer::new(2, 48000, 48000 / 10 * 5); // 0.5s of silence
+        meter.process(&input).unwrap();
+
+        assert_eq!(meter.get_parameter("lufs_integrated"), Some(f32::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_meter_tracks_true_peak() {
+        let mut meter = LoudnessMeterNode::new("meter1".to_string());
+
+        let mut input = AudioBuffer::new(2, 48000, 4);
+        input.samples = vec![0.9, -0.9, 0.1, 0.1, -0.5, 0.5, 0.2, 0.2];
+
+        meter.process(&input).unwrap();
+        assert!(meter.get_parameter("true_peak_db").unwrap() < 0.0);
+    }
+}
@@ -1,80 +1,365 @@
-use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, Result};
+use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
+use std::collections::VecDeque;
 
-/// Mixer node that combines multiple inputs
-/// 
-/// This node demonstrates how the audio graph system enables multiple
-/// connections to flow into a single node, essential for mixing scenarios.
+/// Number of frames the limiter looks ahead before committing to an output
+/// sample, so gain reduction can be ramped in before a transient arrives
+/// rather than clamped after the fact
+const LOOKAHEAD_FRAMES: usize = 64;
+
+/// Soft-knee width (in dB) around the compressor/limiter threshold
+const KNEE_DB: f32 = 6.0;
+
+/// Mix bus: sums inputs (already summed upstream by the graph), then applies
+/// bus gain staging, an optional bus compressor, a look-ahead brickwall
+/// limiter, and a stereo-correlation meter.
+///
+/// Note: the audio graph mixes all of a node's incoming connections into a
+/// single buffer before calling `process`, so per-input trim gains
+/// (`input_N_gain`) aren't applied here - `AudioGraph::gather_inputs` reads
+/// them straight off this node via `get_parameter` and trims each incoming
+/// connection's buffer before it's summed, since that's the only point
+/// where an individual input still exists in isolation. This node just
+/// stores the values so they're settable/gettable/serializable like any
+/// other parameter.
 pub struct MixerNode {
     id: String,
     num_inputs: usize,
+
+    input_gains_db: Vec<f32>,
+
+    bus_gain_db: f32,
+    bus_gain_linear: f32,
+
+    comp_threshold_db: f32,
+    comp_ratio: f32,
+    comp_attack_ms: f32,
+    comp_release_ms: f32,
+    comp_envelope: f32,
+
+    limiter_ceiling_db: f32,
+    limiter_envelope: f32,
+    delay_line: VecDeque<f32>,
+    peak_window: VecDeque<f32>,
+
+    correlation: f32,
+    sample_rate: f32,
+    channels: usize,
 }
 
 impl MixerNode {
     /// Create a new mixer node
-    /// 
+    ///
     /// # Arguments
     /// * `id` - Unique identifier
-    /// * `num_inputs` - Expected number of inputs (for metadata)
+    /// * `num_inputs` - Expected number of inputs (for metadata and per-input gain parameters)
     pub fn new(id: String, num_inputs: usize) -> Self {
-        Self {
+        let mut node = Self {
             id,
             num_inputs,
+            input_gains_db: vec![0.0; num_inputs],
+            bus_gain_db: 0.0,
+            bus_gain_linear: 1.0,
+            comp_threshold_db: 0.0,
+            comp_ratio: 1.0,
+            comp_attack_ms: 10.0,
+            comp_release_ms: 100.0,
+            comp_envelope: 0.0,
+            limiter_ceiling_db: -0.3,
+            limiter_envelope: 1.0,
+            delay_line: VecDeque::new(),
+            peak_window: VecDeque::new(),
+            correlation: 1.0,
+            sample_rate: 48000.0,
+            channels: 2,
+        };
+        node.reset_delay_line();
+        node
+    }
+
+    fn db_to_linear(db: f32) -> f32 {
+        10.0_f32.powf(db / 20.0)
+    }
+
+    fn linear_to_db(linear: f32) -> f32 {
+        20.0 * linear.max(1e-9).log10()
+    }
+
+    /// Soft-knee gain reduction, in dB, for a signal at `level_db` against `threshold_db`/`ratio`
+    fn gain_reduction_db(level_db: f32, threshold_db: f32, ratio: f32) -> f32 {
+        let overshoot = level_db - threshold_db;
+        if overshoot <= -KNEE_DB / 2.0 {
+            0.0
+        } else if overshoot >= KNEE_DB / 2.0 {
+            overshoot - overshoot / ratio
+        } else {
+            let knee_overshoot = overshoot + KNEE_DB / 2.0;
+            (1.0 - 1.0 / ratio) * (knee_overshoot * knee_overshoot) / (2.0 * KNEE_DB)
         }
     }
+
+    /// (Re)fill the lookahead delay line with silence so the limiter always
+    /// has a full window to pop from, even on the very first block
+    fn reset_delay_line(&mut self) {
+        self.delay_line = VecDeque::from(vec![0.0; LOOKAHEAD_FRAMES * self.channels]);
+        self.peak_window = VecDeque::from(vec![0.0; LOOKAHEAD_FRAMES]);
+    }
+
+    fn input_gain_param(index: usize) -> String {
+        format!("input_{}_gain", index)
+    }
 }
 
 impl AudioNode for MixerNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "MixerNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
+        let mut parameters: Vec<ParameterDescriptor> = (0..self.num_inputs)
+            .map(|i| ParameterDescriptor::new(&Self::input_gain_param(i), -60.0, 24.0, 0.0, "dB", &format!("Input {} Gain", i + 1)))
+            .collect();
+
+        parameters.extend([
+            ParameterDescriptor::new("bus_gain", -60.0, 24.0, 0.0, "dB", "Bus Gain"),
+            ParameterDescriptor::new("comp_threshold", -60.0, 0.0, 0.0, "dB", "Comp Threshold"),
+            ParameterDescriptor::new("comp_ratio", 1.0, 20.0, 1.0, ":1", "Comp Ratio"),
+            ParameterDescriptor::new("comp_attack", 0.1, 100.0, 10.0, "ms", "Comp Attack"),
+            ParameterDescriptor::new("comp_release", 10.0, 1000.0, 100.0, "ms", "Comp Release"),
+            ParameterDescriptor::new("limiter_ceiling", -12.0, 0.0, -0.3, "dB", "Limiter Ceiling"),
+        ]);
+
         NodeMetadata {
             name: format!("{}-Input Mixer", self.num_inputs),
             category: NodeCategory::Routing,
             input_channels: 2,
             output_channels: 2,
-            parameters: vec![],  // Mixer has no parameters, just sums inputs
+            parameters,
             plugin: "orquestador".to_string(),
         }
     }
-    
+
     fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
-        // The audio graph system handles mixing multiple inputs before
-        // calling this function, so we just pass through the mixed result
-        Ok(input.clone())
+        if self.channels != input.channels {
+            self.channels = input.channels;
+            self.reset_delay_line();
+        }
+        self.sample_rate = input.sample_rate as f32;
+
+        let mut output = input.clone();
+
+        // Bus gain
+        for sample in &mut output.samples {
+            *sample *= self.bus_gain_linear;
+        }
+
+        // Bus compressor: one envelope follower driven by the loudest channel per frame
+        let comp_attack_coeff = (-1.0 / (self.comp_attack_ms * self.sample_rate / 1000.0)).exp();
+        let comp_release_coeff = (-1.0 / (self.comp_release_ms * self.sample_rate / 1000.0)).exp();
+
+        for frame in output.samples.chunks_mut(self.channels) {
+            let peak = frame.iter().fold(0.0f32, |acc, &s| acc.max(s.abs()));
+            let coeff = if peak > self.comp_envelope { comp_attack_coeff } else { comp_release_coeff };
+            self.comp_envelope = peak + coeff * (self.comp_envelope - peak);
+
+            let reduction_db = Self::gain_reduction_db(
+                Self::linear_to_db(self.comp_envelope),
+                self.comp_threshold_db,
+                self.comp_ratio,
+            );
+            let gain = Self::db_to_linear(-reduction_db);
+
+            for sample in frame.iter_mut() {
+                *sample *= gain;
+            }
+        }
+
+        // Stereo correlation meter (post bus gain/compressor, pre-limiter)
+        if self.channels >= 2 {
+            let (mut sum_lr, mut sum_ll, mut sum_rr) = (0.0f32, 0.0f32, 0.0f32);
+            for frame in output.samples.chunks(self.channels) {
+                let (l, r) = (frame[0], frame[1]);
+                sum_lr += l * r;
+                sum_ll += l * l;
+                sum_rr += r * r;
+            }
+
+            self.correlation = if sum_ll > 0.0 && sum_rr > 0.0 {
+                (sum_lr / (sum_ll * sum_rr).sqrt()).clamp(-1.0, 1.0)
+            } else {
+                1.0
+            };
+        }
+
+        // Look-ahead brickwall limiter
+        let ceiling_linear = Self::db_to_linear(self.limiter_ceiling_db);
+        let limiter_release_coeff = (-1.0 / (50.0 * self.sample_rate / 1000.0)).exp();
+        let mut limited = Vec::with_capacity(output.samples.len());
+
+        for frame in output.samples.chunks(self.channels) {
+            let frame_peak = frame.iter().fold(0.0f32, |acc, &s| acc.max(s.abs()));
+            self.peak_window.push_back(frame_peak);
+            self.peak_window.pop_front();
+
+            let lookahead_peak = self.peak_window.iter().cloned().fold(0.0f32, f32::max);
+            let required_gain = if lookahead_peak > ceiling_linear {
+                ceiling_linear / lookahead_peak
+            } else {
+                1.0
+            };
+
+            // Limiter envelope only ever eases back up (release); it jumps
+            // down instantly so transients found by the lookahead are
+            // always caught in time.
+            self.limiter_envelope = if required_gain < self.limiter_envelope {
+                required_gain
+            } else {
+                required_gain + limiter_release_coeff * (self.limiter_envelope - required_gain)
+            };
+
+            for &sample in frame {
+                self.delay_line.push_back(sample);
+            }
+
+            for _ in 0..self.channels {
+                let delayed = self.delay_line.pop_front().unwrap_or(0.0);
+                limited.push(delayed * self.limiter_envelope);
+            }
+        }
+
+        output.samples = limited;
+        Ok(output)
     }
-    
-    fn set_parameter(&mut self, _name: &str, _value: f32) -> Result<()> {
-        // No parameters
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+        if let Some(index) = (0..self.num_inputs).find(|&i| name == Self::input_gain_param(i)) {
+            self.input_gains_db[index] = value.clamp(-60.0, 24.0);
+            return Ok(());
+        }
+
+        match name {
+            "bus_gain" => {
+                self.bus_gain_db = value.clamp(-60.0, 24.0);
+                self.bus_gain_linear = Self::db_to_linear(self.bus_gain_db);
+            }
+            "comp_threshold" => self.comp_threshold_db = value.clamp(-60.0, 0.0),
+            "comp_ratio" => self.comp_ratio = value.clamp(1.0, 20.0),
+            "comp_attack" => self.comp_attack_ms = value.clamp(0.1, 100.0),
+            "comp_release" => self.comp_release_ms = value.clamp(10.0, 1000.0),
+            "limiter_ceiling" => self.limiter_ceiling_db = value.clamp(-12.0, 0.0),
+            _ => return Err(GraphError::ParameterNotFound(name.to_string())),
+        }
         Ok(())
     }
-    
-    fn get_parameter(&self, _name: &str) -> Option<f32> {
-        None
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        if let Some(index) = (0..self.num_inputs).find(|&i| name == Self::input_gain_param(i)) {
+            return Some(self.input_gains_db[index]);
+        }
+
+        match name {
+            "bus_gain" => Some(self.bus_gain_db),
+            "comp_threshold" => Some(self.comp_threshold_db),
+            "comp_ratio" => Some(self.comp_ratio),
+            "comp_attack" => Some(self.comp_attack_ms),
+            "comp_release" => Some(self.comp_release_ms),
+            "limiter_ceiling" => Some(self.limiter_ceiling_db),
+            "correlation" => Some(self.correlation),
+            _ => None,
+        }
+    }
+
+    fn latency(&self) -> usize {
+        LOOKAHEAD_FRAMES
+    }
+
+    fn reset(&mut self) {
+        self.comp_envelope = 0.0;
+        self.limiter_envelope = 1.0;
+        self.correlation = 1.0;
+        self.reset_delay_line();
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
     #[test]
     fn test_mixer_creation() {
         let mixer = MixerNode::new("mixer1".to_string(), 4);
         assert_eq!(mixer.id(), "mixer1");
         assert_eq!(mixer.num_inputs, 4);
     }
-    
+
     #[test]
-    fn test_mixer_passthrough() {
+    fn test_mixer_passthrough_shape() {
         let mut mixer = MixerNode::new("mixer1".to_string(), 2);
-        
+
         let input = AudioBuffer::new(2, 48000, 512);
         let output = mixer.process(&input).unwrap();
-        
+
         assert_eq!(output.channels, input.channels);
         assert_eq!(output.sample_rate, input.sample_rate);
         assert_eq!(output.samples.len(), input.samples.len());
     }
+
+    #[test]
+    fn test_limiter_keeps_peaks_under_ceiling() {
+        let mut mixer = MixerNode::new("mixer1".to_string(), 1);
+        mixer.set_parameter("limiter_ceiling", -1.0).unwrap();
+
+        let mut input = AudioBuffer::new(2, 48000, 256);
+        input.samples = vec![1.0; input.samples.len()];
+
+        // Push several blocks through so the lookahead window fully fills with the loud signal
+        let mut output = mixer.process(&input).unwrap();
+        for _ in 0..4 {
+            output = mixer.process(&input).unwrap();
+        }
+
+        let ceiling_linear = MixerNode::db_to_linear(-1.0);
+        assert!(output.samples.iter().all(|&s| s.abs() <= ceiling_linear + 0.01));
+    }
+
+    #[test]
+    fn test_correlation_in_phase_signal() {
+        let mut mixer = MixerNode::new("mixer1".to_string(), 1);
+
+        let mut input = AudioBuffer::new(2, 48000, 128);
+        input.samples = vec![0.5; input.samples.len()]; // identical L/R, perfectly correlated
+
+        mixer.process(&input).unwrap();
+
+        let corr = mixer.get_parameter("correlation").unwrap();
+        assert!((corr - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_correlation_out_of_phase_signal() {
+        let mut mixer = MixerNode::new("mixer1".to_string(), 1);
+
+        let mut input = AudioBuffer::new(2, 48000, 128);
+        for frame in input.samples.chunks_mut(2) {
+            frame[0] = 0.5;
+            frame[1] = -0.5;
+        }
+
+        mixer.process(&input).unwrap();
+
+        let corr = mixer.get_parameter("correlation").unwrap();
+        assert!((corr + 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_input_gain_parameter_roundtrip() {
+        let mut mixer = MixerNode::new("mixer1".to_string(), 3);
+
+        mixer.set_parameter("input_1_gain", -6.0).unwrap();
+        assert_eq!(mixer.get_parameter("input_1_gain"), Some(-6.0));
+        assert_eq!(mixer.get_parameter("input_0_gain"), Some(0.0));
+    }
 }
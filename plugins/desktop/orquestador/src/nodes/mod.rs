@@ -1,7 +1,11 @@
 pub mod channel_strip;
 pub mod pan;
 pub mod mixer;
+pub mod loudness_meter;
+pub mod resampler;
 
 pub use channel_strip::ChannelStripNode;
 pub use pan::PanNode;
 pub use mixer::MixerNode;
+pub use loudness_meter::LoudnessMeterNode;
+pub use resampler::ResamplerNode;
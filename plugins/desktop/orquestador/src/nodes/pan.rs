@@ -1,42 +1,47 @@
-use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
-use std::f32::consts::FRAC_PI_4;
+use espectro::{AudioNode, AudioBuffer, ChannelLayout, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError, SmoothedParameter};
 
-/// Stereo panning node
-/// 
-/// Implements constant-power panning for smooth stereo imaging.
+/// Default ramp time for the `pan` parameter, long enough to eliminate
+/// zipper noise on automation without making fast pan moves feel sluggish
+const DEFAULT_PAN_SMOOTHING_MS: f32 = 15.0;
+
+/// Panning node
+///
+/// Implements constant-power panning for smooth stereo imaging. For layouts
+/// beyond plain stereo, only the front left/right pair is panned (see
+/// `ChannelLayout::pan_gains`); mono input is panned as a single attenuated
+/// channel since there's no left/right pair to split it across.
 /// Can be used standalone or as part of a ChannelStripNode.
+///
+/// `pan` is smoothed (see `SmoothedParameter`): `process` recomputes the
+/// gains once per frame from the interpolated value instead of once per
+/// block, so automating pan doesn't click.
 pub struct PanNode {
     id: String,
-    pan: f32,  // -1.0 (left) to 1.0 (right)
+    pan: SmoothedParameter,  // -1.0 (left) to 1.0 (right)
 }
 
 impl PanNode {
     /// Create a new pan node
-    /// 
+    ///
     /// # Arguments
     /// * `id` - Unique identifier
     pub fn new(id: String) -> Self {
         Self {
             id,
-            pan: 0.0,  // Center
+            pan: SmoothedParameter::new(0.0, DEFAULT_PAN_SMOOTHING_MS),  // Center
         }
     }
-    
-    /// Calculate constant-power pan gains
-    fn calculate_gains(&self) -> (f32, f32) {
-        // Constant power panning
-        let pan_angle = (self.pan + 1.0) * FRAC_PI_4;  // 0 to PI/2
-        let left_gain = pan_angle.cos();
-        let right_gain = pan_angle.sin();
-        (left_gain, right_gain)
-    }
 }
 
 impl AudioNode for PanNode {
     fn id(&self) -> &str {
         &self.id
     }
-    
+
+    fn node_type(&self) -> &str {
+        "PanNode"
+    }
+
     fn metadata(&self) -> NodeMetadata {
         NodeMetadata {
             name: "Pan".to_string(),
@@ -51,41 +56,51 @@ impl AudioNode for PanNode {
                     0.0,
                     "",
                     "Pan"
-                ),
+                ).with_smoothing(DEFAULT_PAN_SMOOTHING_MS),
             ],
             plugin: "orquestador".to_string(),
         }
     }
-    
+
     fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
         let mut output = input.clone();
-        let (left_gain, right_gain) = self.calculate_gains();
-        
-        // Apply panning to stereo samples
-        for i in (0..output.samples.len()).step_by(2) {
-            let left = output.samples[i];
-            let right = output.samples[i + 1];
-            
-            output.samples[i] = left * left_gain;
-            output.samples[i + 1] = right * right_gain;
+        let layout = ChannelLayout::from_channel_count(output.channels).unwrap_or(ChannelLayout::Stereo);
+        self.pan.prepare(input.sample_rate);
+
+        for frame in output.samples.chunks_mut(output.channels) {
+            let pan_gains = layout.pan_gains(self.pan.next());
+            for (channel, sample) in frame.iter_mut().enumerate() {
+                *sample *= pan_gains.get(channel).copied().unwrap_or(1.0);
+            }
         }
-        
+
         Ok(output)
     }
-    
+
     fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
         match name {
             "pan" => {
-                self.pan = value.clamp(-1.0, 1.0);
+                self.pan.set_immediate(value.clamp(-1.0, 1.0));
                 Ok(())
             }
             _ => Err(GraphError::ParameterNotFound(name.to_string())),
         }
     }
-    
+
+    fn set_parameter_smoothed(&mut self, name: &str, value: f32, ramp_ms: f32) -> Result<()> {
+        match name {
+            "pan" => {
+                self.pan.set_smoothing_ms(ramp_ms);
+                self.pan.set_target(value.clamp(-1.0, 1.0));
+                Ok(())
+            }
+            _ => Err(GraphError::ParameterNotFound(name.to_string())),
+        }
+    }
+
     fn get_parameter(&self, name: &str) -> Option<f32> {
         match name {
-            "pan" => Some(self.pan),
+            "pan" => Some(self.pan.target()),
             _ => None,
         }
     }
@@ -135,4 +150,38 @@ mod tests {
         // Right should be louder than left
         assert!(output.samples[1] > output.samples[0]);
     }
+
+    #[test]
+    fn test_set_parameter_smoothed_ramps_instead_of_snapping() {
+        let mut node = PanNode::new("pan1".to_string());
+        node.set_parameter_smoothed("pan", 1.0, 20.0).unwrap();
+
+        let mut input = AudioBuffer::new(2, 48000, 8);
+        input.samples = vec![1.0; 16];
+
+        let output = node.process(&input).unwrap();
+
+        // First frame hasn't caught up to a full right pan yet...
+        assert!(output.samples[0] > 0.0);
+        // ...but later frames in the same block have moved further toward it.
+        let first_left = output.samples[0];
+        let last_left = output.samples[14];
+        assert!(last_left < first_left);
+    }
+
+    #[test]
+    fn test_pan_surround_leaves_center_and_lfe_untouched() {
+        let mut node = PanNode::new("pan1".to_string());
+        node.set_parameter("pan", 1.0).unwrap();  // Full right
+
+        // 5.1: [FL, FR, FC, LFE, SL, SR]
+        let mut input = AudioBuffer::new(6, 48000, 1);
+        input.samples = vec![1.0, 1.0, 1.0, 1.0, 1.0, 1.0];
+
+        let output = node.process(&input).unwrap();
+
+        assert_eq!(output.samples[2], 1.0); // FC untouched
+        assert_eq!(output.samples[3], 1.0); // LFE untouched
+        assert!(output.samples[1] > output.samples[0]); // FR louder than FL
+    }
 }
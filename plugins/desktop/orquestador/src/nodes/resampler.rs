@@ -0,0 +1,466 @@
+use espectro::{AudioNode, AudioBuffer, NodeMetadata, NodeCategory, ParameterDescriptor, Result, GraphError};
+
+/// Fractional playback position, stepping through the input at `src_rate /
+/// dst_rate` per output sample
+#[derive(Clone, Copy, Debug, Default)]
+struct FracPos {
+    ipos: usize,
+    frac: usize,
+}
+
+const SINC_ZERO_CROSSINGS: usize = 8;
+const FRAC_DENOM: usize = 1 << 16;
+/// Number of taps in a single convolution, e.g. the offsets `[-7, .. 8]` for
+/// 8 zero crossings.
+const TAPS: usize = 2 * SINC_ZERO_CROSSINGS;
+/// Number of precomputed fractional-delay phases in the polyphase filter
+/// bank. Each output sample is produced by convolving against whichever
+/// phase's kernel is nearest its true fractional position, rather than
+/// evaluating the windowed sinc from scratch per sample.
+const FILTER_PHASES: usize = 64;
+
+fn sinc(x: f32) -> f32 {
+    if x.abs() < 1e-8 {
+        1.0
+    } else {
+        let px = std::f32::consts::PI * x;
+        px.sin() / px
+    }
+}
+
+/// Blackman-windowed sinc kernel value for a fractional offset
+fn windowed_sinc(x: f32) -> f32 {
+    let n = SINC_ZERO_CROSSINGS as f32;
+    if x.abs() >= n {
+        return 0.0;
+    }
+    let window = 0.42 - 0.5 * (std::f32::consts::PI * (x / n + 1.0)).cos()
+        + 0.08 * (2.0 * std::f32::consts::PI * (x / n + 1.0)).cos();
+    sinc(x) * window
+}
+
+/// The frame offsets (relative to the output position) a convolution reads,
+/// e.g. `-7..=8` for 8 zero crossings.
+fn tap_offsets() -> impl Iterator<Item = i64> {
+    let lo = -(SINC_ZERO_CROSSINGS as i64) + 1;
+    let hi = SINC_ZERO_CROSSINGS as i64;
+    lo..=hi
+}
+
+/// Precompute the `FILTER_PHASES x TAPS` polyphase filter bank: each row is
+/// the windowed-sinc kernel for one fractional delay.
+fn build_filter_bank() -> Vec<[f32; TAPS]> {
+    (0..FILTER_PHASES)
+        .map(|phase| {
+            let phase_frac = phase as f32 / FILTER_PHASES as f32;
+            let mut row = [0.0f32; TAPS];
+            for (i, tap) in tap_offsets().enumerate() {
+                row[i] = windowed_sinc(tap as f32 - phase_frac);
+            }
+            row
+        })
+        .collect()
+}
+
+/// Largest common divisor of two sample-rate-derived integers, used to
+/// reduce an input/output rate pair to a coprime `L/M` ratio before
+/// stepping through the polyphase filter bank.
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Converts an input buffer from its own sample rate to a configured target
+/// rate, so the compositor can mix sources recorded at different rates.
+pub struct ResamplerNode {
+    id: String,
+    target_rate: u32,
+    /// When nonzero, caps the output rate at this ceiling instead of forcing
+    /// `target_rate`: inputs at or below the ceiling pass through unchanged,
+    /// bounding CPU on hi-res sources without discarding headroom on
+    /// everything else.
+    max_rate: u32,
+    /// 0.0 = linear (fast/low-latency), 1.0 = windowed-sinc (high quality)
+    quality: f32,
+
+    pos: FracPos,
+    /// Interleaved samples not yet fully consumed, carried across `process`
+    /// calls. This holds both the trailing history a convolution's negative
+    /// taps read (so block boundaries don't click) and the leading tail
+    /// whose positive taps reach past the end of the block that produced it
+    /// (so a convolution near a block's end isn't zero-padded with samples
+    /// that simply haven't arrived yet). An output frame is only emitted
+    /// once every tap it needs is actually present in this buffer; this is
+    /// the real delay line backing `latency()`, not just a number it reports.
+    pending: Vec<f32>,
+    channels: usize,
+
+    /// Precomputed polyphase filter bank (see `build_filter_bank`)
+    filter_bank: Vec<[f32; TAPS]>,
+}
+
+impl ResamplerNode {
+    pub fn new(id: String, target_rate: u32) -> Self {
+        Self {
+            id,
+            target_rate,
+            max_rate: 0,
+            quality: 1.0,
+            pos: FracPos::default(),
+            pending: Vec::new(),
+            channels: 2,
+            filter_bank: build_filter_bank(),
+        }
+    }
+
+    /// How many frames of trailing history to retain behind `pos.ipos` once
+    /// a block has been fully processed, so the next block's negative taps
+    /// have real samples to read instead of falling back to silence.
+    fn history_frames(&self) -> usize {
+        SINC_ZERO_CROSSINGS + 1
+    }
+
+    /// The furthest a convolution reaches past its center frame: determines
+    /// how many frames of lookahead must be buffered before an output frame
+    /// at a given position can be produced.
+    fn max_positive_tap(&self) -> usize {
+        if self.quality >= 0.5 {
+            SINC_ZERO_CROSSINGS
+        } else {
+            1
+        }
+    }
+
+    /// The rate this node actually converts to for a given input: `max_rate`
+    /// (when set) only kicks in above the ceiling, otherwise every input is
+    /// forced to `target_rate`.
+    fn effective_target_rate(&self, input_rate: u32) -> u32 {
+        if self.max_rate > 0 {
+            input_rate.min(self.max_rate)
+        } else {
+            self.target_rate
+        }
+    }
+
+    /// Filter bank row for whichever phase is nearest a fractional position
+    /// in `[0, 1)`
+    fn nearest_phase(&self, frac: f32) -> &[f32; TAPS] {
+        let index = ((frac * FILTER_PHASES as f32).round() as usize).min(FILTER_PHASES - 1);
+        &self.filter_bank[index]
+    }
+
+    /// Fetch interleaved sample at a frame index (relative to `self.pending`)
+    /// and channel `ch`. Indices before the start of `pending` (which can
+    /// only happen during the stream's very first block, before any history
+    /// has accumulated) read as silence.
+    fn sample_at(&self, frame_index: i64, ch: usize) -> f32 {
+        if frame_index < 0 {
+            return 0.0;
+        }
+        let idx = frame_index as usize * self.channels.max(1) + ch;
+        self.pending.get(idx).copied().unwrap_or(0.0)
+    }
+
+    fn interpolate(&self, frame: i64, frac: f32, ch: usize) -> f32 {
+        if self.quality < 0.5 {
+            // Linear interpolation (fast/low-latency mode)
+            let a = self.sample_at(frame, ch);
+            let b = self.sample_at(frame + 1, ch);
+            a + (b - a) * frac
+        } else {
+            // Polyphase windowed-sinc convolution (quality mode): pick the
+            // filter bank row nearest this fractional position and convolve
+            // against the neighboring input samples it was built for.
+            let kernel = self.nearest_phase(frac);
+            let mut acc = 0.0f32;
+            for (i, tap) in tap_offsets().enumerate() {
+                let sample = self.sample_at(frame + tap, ch);
+                acc += sample * kernel[i];
+            }
+            acc
+        }
+    }
+
+    /// Drop frames from the front of `pending` that are fully behind both
+    /// `pos.ipos` and the trailing history margin, rebasing `pos.ipos` to
+    /// match. Keeps `pending` from growing unboundedly across calls.
+    fn trim_pending(&mut self) {
+        let keep_from_frame = self.pos.ipos.saturating_sub(self.history_frames());
+        if keep_from_frame == 0 {
+            return;
+        }
+        let channels = self.channels.max(1);
+        self.pending.drain(..keep_from_frame * channels);
+        self.pos.ipos -= keep_from_frame;
+    }
+}
+
+impl AudioNode for ResamplerNode {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn node_type(&self) -> &str {
+        "ResamplerNode"
+    }
+
+    fn metadata(&self) -> NodeMetadata {
+        NodeMetadata {
+            name: "Resampler".to_string(),
+            category: NodeCategory::Routing,
+            input_channels: 2,
+            output_channels: 2,
+            parameters: vec![
+                ParameterDescriptor::new(
+                    "target_rate",
+                    8000.0,
+                    192000.0,
+                    self.target_rate as f32,
+                    "Hz",
+                    "Target Sample Rate",
+                ),
+                ParameterDescriptor::new(
+                    "max_rate",
+                    0.0,
+                    192000.0,
+                    self.max_rate as f32,
+                    "Hz",
+                    "Max Rate Ceiling (0=disabled, use Target Rate)",
+                ),
+                ParameterDescriptor::new(
+                    "quality",
+                    0.0,
+                    1.0,
+                    1.0,
+                    "",
+                    "Quality (0=linear, 1=sinc)",
+                ),
+            ],
+            plugin: "orquestador".to_string(),
+        }
+    }
+
+    fn process(&mut self, input: &AudioBuffer) -> Result<AudioBuffer> {
+        let dst_rate = self.effective_target_rate(input.sample_rate);
+        self.channels = input.channels;
+
+        if input.sample_rate == dst_rate {
+            return Ok(input.clone());
+        }
+
+        self.pending.extend_from_slice(&input.samples);
+
+        // Reduce to a coprime L/M ratio. The per-sample step M/L is held in
+        // fixed point (FRAC_DENOM units per input frame) and rounded down, so
+        // it is exact only when L divides M * FRAC_DENOM.
+        let divisor = gcd(input.sample_rate as u64, dst_rate as u64).max(1);
+        let l = (dst_rate as u64 / divisor) as usize; // upsample factor
+        let m = (input.sample_rate as u64 / divisor) as usize; // decimate factor
+
+        let max_tap = self.max_positive_tap();
+        let total_frames = self.pending.len() / self.channels.max(1);
+
+        let mut out_samples = Vec::new();
+        while self.pos.ipos + max_tap < total_frames {
+            let frac = self.pos.frac as f32 / FRAC_DENOM as f32;
+
+            for ch in 0..input.channels {
+                out_samples.push(self.interpolate(self.pos.ipos as i64, frac, ch));
+            }
+
+            // Advance the fractional position by M/L input samples per
+            // output sample (conceptually: zero-stuff by L, low-pass, then
+            // decimate by M, with only the nonzero polyphase taps evaluated)
+            self.pos.frac += (m * FRAC_DENOM) / l;
+            self.pos.ipos += self.pos.frac / FRAC_DENOM;
+            self.pos.frac %= FRAC_DENOM;
+        }
+
+        self.trim_pending();
+
+        Ok(AudioBuffer {
+            channels: input.channels,
+            sample_rate: dst_rate,
+            samples: out_samples,
+        })
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<()> {
+        match name {
+            "target_rate" => {
+                self.target_rate = value.clamp(8000.0, 192000.0) as u32;
+            }
+            "max_rate" => {
+                self.max_rate = value.clamp(0.0, 192000.0) as u32;
+            }
+            "quality" => {
+                self.quality = value.clamp(0.0, 1.0);
+            }
+            _ => return Err(GraphError::ParameterNotFound(name.to_string())),
+        }
+        Ok(())
+    }
+
+    fn get_parameter(&self, name: &str) -> Option<f32> {
+        match name {
+            "target_rate" => Some(self.target_rate as f32),
+            "max_rate" => Some(self.max_rate as f32),
+            "quality" => Some(self.quality),
+            _ => None,
+        }
+    }
+
+    fn latency(&self) -> usize {
+        if self.quality >= 0.5 {
+            SINC_ZERO_CROSSINGS
+        } else {
+            0
+        }
+    }
+
+    fn reset(&mut self) {
+        self.pos = FracPos::default();
+        self.pending.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resampler_creation() {
+        let node = ResamplerNode::new("resampler1".to_string(), 48000);
+        assert_eq!(node.get_parameter("target_rate"), Some(48000.0));
+    }
+
+    #[test]
+    fn test_resampler_passthrough_when_rate_matches() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 48000);
+
+        let input = AudioBuffer::new(2, 48000, 256);
+        let output = node.process(&input).unwrap();
+
+        assert_eq!(output.sample_rate, 48000);
+        assert_eq!(output.samples.len(), input.samples.len());
+    }
+
+    #[test]
+    fn test_resampler_changes_frame_count() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 48000);
+
+        // Feed enough blocks that the lookahead delay line has real future
+        // samples to draw on, rather than asserting on the very first
+        // (still-draining) block.
+        let input = AudioBuffer::new(2, 44100, 441);
+        let mut total_out_frames = 0;
+        for _ in 0..4 {
+            let output = node.process(&input).unwrap();
+            assert_eq!(output.sample_rate, 48000);
+            total_out_frames += output.num_frames();
+        }
+        // 4 * 441 frames at 44100 -> ~4 * 480 frames at 48000
+        assert!(total_out_frames > 4 * input.num_frames());
+    }
+
+    #[test]
+    fn test_filter_bank_has_expected_phases_and_taps() {
+        let node = ResamplerNode::new("resampler1".to_string(), 48000);
+        assert_eq!(node.filter_bank.len(), FILTER_PHASES);
+
+        // Phase 0 (zero fractional offset) is the unwindowed kernel's peak:
+        // the center tap (offset 0) should line up with the input sample.
+        let center_tap = tap_offsets().position(|t| t == 0).unwrap();
+        assert!((node.filter_bank[0][center_tap] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resampler_output_has_no_nan_or_inf() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 44100);
+
+        let mut input = AudioBuffer::new(2, 48000, 512);
+        for (i, sample) in input.samples.iter_mut().enumerate() {
+            *sample = (i as f32 * 0.05).sin();
+        }
+
+        let output = node.process(&input).unwrap();
+        assert!(output.samples.iter().all(|s| s.is_finite()));
+    }
+
+    #[test]
+    fn test_max_rate_passes_through_when_input_already_under_ceiling() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 48000);
+        node.set_parameter("max_rate", 96000.0).unwrap();
+
+        let input = AudioBuffer::new(2, 44100, 256);
+        let output = node.process(&input).unwrap();
+
+        // Input is already below the ceiling, so max_rate mode leaves it alone
+        // rather than forcing it up to target_rate.
+        assert_eq!(output.sample_rate, 44100);
+        assert_eq!(output.samples.len(), input.samples.len());
+    }
+
+    #[test]
+    fn test_max_rate_caps_hi_res_input() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 48000);
+        node.set_parameter("max_rate", 48000.0).unwrap();
+
+        let input = AudioBuffer::new(2, 96000, 960);
+        let output = node.process(&input).unwrap();
+
+        assert_eq!(output.sample_rate, 48000);
+        assert!(output.num_frames() < input.num_frames());
+    }
+
+    #[test]
+    fn test_linear_quality_mode_has_no_latency() {
+        let mut node = ResamplerNode::new("resampler1".to_string(), 44100);
+        node.set_parameter("quality", 0.0).unwrap();
+
+        assert_eq!(node.latency(), 0);
+
+        let input = AudioBuffer::new(1, 48000, 64);
+        let output = node.process(&input).unwrap();
+        assert_eq!(output.sample_rate, 44100);
+    }
+
+    #[test]
+    fn test_sinc_mode_does_not_zero_pad_block_tail() {
+        // A block boundary landing mid-convolution must borrow real samples
+        // from the next block via the pending lookahead buffer, not silence.
+        // Feed a sustained sine across several blocks and check the tail of
+        // each block's output isn't attenuated toward zero relative to its
+        // body, which is what zero-padded future taps would produce.
+        let mut node = ResamplerNode::new("resampler1".to_string(), 48000);
+
+        let mut peak_near_tail = 0.0f32;
+        let mut peak_overall = 0.0f32;
+        for block in 0..6 {
+            let mut input = AudioBuffer::new(1, 44100, 64);
+            for (i, sample) in input.samples.iter_mut().enumerate() {
+                let t = (block * 64 + i) as f32;
+                *sample = (t * 0.2).sin();
+            }
+            let output = node.process(&input).unwrap();
+            let frames = output.num_frames();
+            if frames >= 4 {
+                for &s in &output.samples[frames - 4..] {
+                    peak_near_tail = peak_near_tail.max(s.abs());
+                }
+            }
+            for &s in &output.samples {
+                peak_overall = peak_overall.max(s.abs());
+            }
+        }
+
+        assert!(peak_overall > 0.0);
+        // A block-boundary artifact from zero-padding would drive the last
+        // few samples of (most) blocks toward zero; with real lookahead
+        // samples they should reach comparable amplitude to the rest.
+        assert!(peak_near_tail > peak_overall * 0.3);
+    }
+}